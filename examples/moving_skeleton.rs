@@ -13,6 +13,7 @@ use bevy_inspector_egui::bevy_egui::EguiPlugin;
 
 pub const PLAYER_TAG: &str = "player";
 pub const PLAYER_SPEED: f32 = 200.;
+pub const WALK_DIRECTIONS: &str = "walk";
 
 #[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
 pub enum LoadingGameState {
@@ -25,14 +26,15 @@ fn main() {
 }
 
 fn change_state_on_input(
-    mut query: Query<(&mut AnimationComp, &mut Transform, &mut TextureAtlasSprite), With<Player>>,
+    mut query: Query<(&mut AnimationComp, &mut Transform), With<Player>>,
     time: Res<Time<Virtual>>,
     input: Res<Input<KeyCode>>,
+    repos: Res<AllAnimationResource>,
 ) {
     if time.is_paused() {
         return;
     }
-    let (mut animation, mut location, mut sprite) = query.single_mut();
+    let (mut animation, mut location) = query.single_mut();
     let mut direction = Vec2::ZERO;
     if input.pressed(KeyCode::A) {
         direction.x -= 1.;
@@ -47,17 +49,9 @@ fn change_state_on_input(
         direction.y -= 1.;
     }
     let movement = direction * time.delta_seconds() * PLAYER_SPEED;
-    if direction.x > 0. {
-        animation.change_state(AniStates::Left.to_str());
-        sprite.flip_x = true;
-    } else if direction.x < 0. {
-        animation.change_state(AniStates::Left.to_str());
-        sprite.flip_x = false;
-    } else if direction.y > 0. {
-        animation.change_state(AniStates::Top.to_str());
-    } else {
-        animation.change_state(AniStates::Bottom.to_str());
-    }
+    animation
+        .change_direction(WALK_DIRECTIONS, direction, &repos)
+        .unwrap();
 
     location.translation += Vec3::new(movement.x, movement.y, 0.);
     if direction.length().is_zero() {
@@ -65,24 +59,6 @@ fn change_state_on_input(
     }
 }
 
-pub enum AniStates {
-    Left,
-    Right,
-    Bottom,
-    Top,
-}
-
-impl AniStates {
-    fn to_str(self) -> &'static str {
-        match self {
-            AniStates::Left => "Left",
-            AniStates::Right => "Right",
-            AniStates::Bottom => "Bottom",
-            AniStates::Top => "Top",
-        }
-    }
-}
-
 #[derive(Component)]
 pub struct Player;
 
@@ -181,6 +157,7 @@ fn print_player_animation_status(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn scale_animation_factor(
     cooldown: f32,
 ) -> impl FnMut(
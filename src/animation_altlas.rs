@@ -1,4 +1,4 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 #[cfg(feature = "assets")]
 use serde::Deserialize;
 
@@ -80,6 +80,37 @@ impl AnimationAltlasMeta {
         AnimationAltlas { atlas, meta: self }
     }
 
+    /// Keeps the grid without building a texture atlas,
+    /// for collections whose states only show separate images.
+    pub fn without_atlas(self) -> AnimationAltlas {
+        AnimationAltlas {
+            atlas: Handle::default(),
+            meta: self,
+        }
+    }
+
+    /// Same as [`Self::build`] but reuses the texture atlas from `cache`
+    /// if another collection was already built from the same image and grid.
+    pub fn build_shared(
+        self,
+        image: Handle<Image>,
+        assets_atlas: &mut Assets<TextureAtlas>,
+        cache: &mut TextureAtlasCache,
+    ) -> AnimationAltlas {
+        let key = AtlasCacheKey::new(&self, &image);
+        match cache.0.get(&key) {
+            Some(atlas) if assets_atlas.contains(atlas) => AnimationAltlas {
+                atlas: atlas.clone(),
+                meta: self,
+            },
+            _ => {
+                let built = self.build(image, assets_atlas);
+                cache.0.insert(key, built.atlas());
+                built
+            }
+        }
+    }
+
     pub fn rows(&self) -> usize {
         self.rows
     }
@@ -99,4 +130,90 @@ impl AnimationAltlasMeta {
     pub fn offset(&self) -> Option<Vec2> {
         self.offset
     }
+
+    /// Pixels of the cell at `index` inside the image, like the texture atlas built from this grid.
+    pub fn rect_of(&self, index: AnimationIndex) -> Rect {
+        let columns = self.columns.max(1);
+        let cell = Vec2::new((index % columns) as f32, (index / columns) as f32);
+        let min = (self.cell_size + self.padding.unwrap_or_default()) * cell
+            + self.offset.unwrap_or_default();
+        Rect::from_corners(min, min + self.cell_size)
+    }
+}
+
+/// Texture atlases shared between collections which use the same image and grid.
+#[derive(Debug, Default)]
+pub struct TextureAtlasCache(HashMap<AtlasCacheKey, Handle<TextureAtlas>>);
+
+impl TextureAtlasCache {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Forgets all atlases which are not used by any collection or entity anymore.
+    pub fn prune(&mut self) {
+        self.0.retain(|_, atlas| match atlas {
+            Handle::Strong(strong) => std::sync::Arc::strong_count(strong) > 1,
+            Handle::Weak(_) => false,
+        });
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct AtlasCacheKey {
+    image: AssetId<Image>,
+    rows: AnimationIndex,
+    columns: AnimationIndex,
+    cell_size: [u32; 2],
+    padding: Option<[u32; 2]>,
+    offset: Option<[u32; 2]>,
+}
+
+impl AtlasCacheKey {
+    fn new(meta: &AnimationAltlasMeta, image: &Handle<Image>) -> Self {
+        let to_bits = |vector: Vec2| [vector.x.to_bits(), vector.y.to_bits()];
+        Self {
+            image: image.id(),
+            rows: meta.rows,
+            columns: meta.columns,
+            cell_size: to_bits(meta.cell_size),
+            padding: meta.padding.map(to_bits),
+            offset: meta.offset.map(to_bits),
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn shares_atlas_for_same_image_and_grid() {
+        let mut assets = Assets::<TextureAtlas>::default();
+        let mut cache = TextureAtlasCache::default();
+        let image = Handle::<Image>::default();
+        let meta = AnimationAltlasMeta::new(4, 6, Vec2::splat(64.));
+
+        let first = meta
+            .clone()
+            .build_shared(image.clone(), &mut assets, &mut cache);
+        let second = meta
+            .clone()
+            .build_shared(image.clone(), &mut assets, &mut cache);
+        let other_grid = meta
+            .new_padding(Vec2::ONE)
+            .build_shared(image, &mut assets, &mut cache);
+
+        assert_eq!(first.atlas(), second.atlas());
+        assert_ne!(first.atlas(), other_grid.atlas());
+        assert_eq!(2, cache.len());
+
+        drop((first, second));
+        cache.prune();
+        assert_eq!(1, cache.len());
+    }
 }
@@ -1,24 +1,42 @@
 use std::sync::Arc;
 
-use bevy::{prelude::Handle, sprite::TextureAtlas};
+use bevy::{
+    log::warn,
+    prelude::{Asset, Handle},
+    reflect::TypePath,
+    sprite::TextureAtlas,
+    utils::HashMap,
+};
 
 use crate::{
-    animation_altlas::AnimationAltlas,
-    animation_error::NotFoundError,
+    animation_altlas::{AnimationAltlas, AnimationAltlasMeta},
+    animation_collection_changed::StateChange,
+    animation_error::{AnimationError, NotFoundError},
     animation_frames::AnimationFrames,
+    blend_set::BlendSet,
+    direction_set::DirectionSet,
+    repository_inspection::{CollectionInfo, StateInfo},
     text_like::TextLike,
     types::{
-        self, AnimationDuration, AnimationIndex, AnimationSeqToBuild, AnimationSequence,
-        ImmutableAnimationFrames, KeyLookUpResult,
+        self, AnimationDuration, AnimationIndex, AnimationResult, AnimationSeqToBuild,
+        AnimationSequence, ImmutableAnimationFrames, KeyLookUpResult,
     },
-    utils,
+    utils, PosScaleFactor,
 };
 
-#[derive(Debug)]
+/// All animation states which share one texture atlas.
+/// Can be registered under a key in [`AllAnimationResource`](crate::prelude::AllAnimationResource)
+/// or added as an asset to [`Assets<AnimationCollection>`](bevy::prelude::Assets).
+#[derive(Debug, Asset, TypePath)]
 pub struct AnimationCollection {
     meta: AnimationAltlas,
     start_state: &'static str,
-    pub frames: AnimationSequence,
+    frames: AnimationSequence,
+    time_scale: PosScaleFactor,
+    direction_sets: HashMap<&'static str, DirectionSet>,
+    blend_sets: HashMap<&'static str, BlendSet>,
+    /// Changes since the last time they were propagated to the entities.
+    changes: Vec<StateChange>,
 }
 
 impl std::fmt::Display for AnimationCollection {
@@ -42,6 +60,11 @@ impl AnimationCollection {
         self.meta.atlas()
     }
 
+    /// Grid the texture atlas was built from.
+    pub fn grid(&self) -> &AnimationAltlasMeta {
+        self.meta.data()
+    }
+
     pub fn frames(&self) -> &AnimationSequence {
         &self.frames
     }
@@ -53,14 +76,257 @@ impl AnimationCollection {
             .ok_or_else(|| NotFoundError::SingleAnimation(key.into()))
     }
 
+    /// Replaces all states.
+    /// Differences to the previous states are recorded as changes.
     pub fn set_frames(&mut self, new_seq: AnimationSequence) {
-        self.frames = new_seq;
+        let previous_seq = std::mem::replace(&mut self.frames, new_seq);
+        for (&state, previous) in previous_seq.iter() {
+            match self.frames.get(state) {
+                None => self.changes.push(StateChange::Removed(state)),
+                Some(current) if current != previous => self.changes.push(StateChange::Modified {
+                    state,
+                    previous: previous.clone(),
+                }),
+                _ => (),
+            }
+        }
+        for &state in self.frames.keys() {
+            if !previous_seq.contains_key(state) {
+                self.changes.push(StateChange::Added(state));
+            }
+        }
+    }
+
+    /// Takes over atlas, start state and states of `other`.
+    /// Differences of the states are recorded as changes.
+    pub(crate) fn replace_with(&mut self, other: AnimationCollection) {
+        let atlas_changed = self.atlas() != other.atlas();
+        self.meta = other.meta;
+        self.start_state = other.start_state;
+        self.direction_sets = other.direction_sets;
+        self.blend_sets = other.blend_sets;
+        if atlas_changed {
+            // Every state refers to the new atlas even if its frames stayed the same.
+            let unchanged = self
+                .frames
+                .iter()
+                .filter(|(state, frames)| other.frames.get(*state) == Some(frames))
+                .map(|(&state, frames)| StateChange::Modified {
+                    state,
+                    previous: frames.clone(),
+                })
+                .collect::<Vec<_>>();
+            self.changes.extend(unchanged);
+        }
+        self.set_frames(other.frames);
+    }
+
+    /// Replaces only `previous_states` with `new_states`.
+    /// Previous states which are missing in `new_states` are removed.
+    /// The start state is kept with a warning as a collection can not be without it.
+    pub(crate) fn replace_states(
+        &mut self,
+        previous_states: &[&'static str],
+        new_states: AnimationSequence,
+    ) {
+        for state in previous_states {
+            if new_states.contains_key(state) {
+                continue;
+            }
+            if *state == self.start_state {
+                warn!(
+                    "State ({}) is kept although it was removed from its file because it is the start state of its collection.",
+                    state
+                );
+            } else if let Some((state, _)) = self.frames.remove_entry(state) {
+                self.changes.push(StateChange::Removed(state));
+            }
+        }
+        for (state, frames) in new_states {
+            if self.frames.get(state) != Some(&frames) {
+                self.insert_frames(state, frames);
+            }
+        }
+    }
+
+    /// Adds all states of `other`. States with the same name are replaced.
+    /// Both collections are expected to use the same texture atlas.
+    pub(crate) fn merge(&mut self, other: AnimationCollection) {
+        for (state, frames) in other.frames {
+            self.insert_frames(state, frames);
+        }
+        self.direction_sets.extend(other.direction_sets);
+        self.blend_sets.extend(other.blend_sets);
+    }
+
+    /// Adds a new state or replaces the frames of an existing state.
+    /// Returns the frames which were replaced.
+    pub fn insert_state<'a>(
+        &mut self,
+        key: impl Into<TextLike<'a>>,
+        frames: AnimationFrames,
+    ) -> Option<ImmutableAnimationFrames> {
+        self.insert_frames(key.into().into_registered_name(), Arc::new(frames))
+    }
+
+    fn insert_frames(
+        &mut self,
+        state: &'static str,
+        frames: ImmutableAnimationFrames,
+    ) -> Option<ImmutableAnimationFrames> {
+        let previous = self.frames.insert(state, frames);
+        self.changes.push(match &previous {
+            Some(previous) => StateChange::Modified {
+                state,
+                previous: previous.clone(),
+            },
+            None => StateChange::Added(state),
+        });
+        previous
+    }
+
+    /// # Errors
+    /// Returns an error if there is no state under `key` or if it is the start state.
+    pub fn remove_state(&mut self, key: &str) -> AnimationResult<ImmutableAnimationFrames> {
+        if key == self.start_state {
+            return Err(AnimationError::RemovingStartState(key.to_string()));
+        }
+        let (state, removed) = self
+            .frames
+            .remove_entry(key)
+            .ok_or_else(|| NotFoundError::SingleAnimation(key.into()))?;
+        self.changes.push(StateChange::Removed(state));
+        Ok(removed)
+    }
+
+    /// Renames a state. Entities playing this state keep playing it under the new name.
+    /// # Errors
+    /// Returns an error if there is no state under `from` or a state under `to` already exists.
+    pub fn rename_state<'a>(&mut self, from: &str, to: impl Into<TextLike<'a>>) -> AnimationResult {
+        let to = to.into().into_registered_name();
+        if self.frames.contains_key(to) {
+            return Err(AnimationError::DuplicateState(to.to_string()));
+        }
+        let (from, frames) = self
+            .frames
+            .remove_entry(from)
+            .ok_or_else(|| NotFoundError::SingleAnimation(from.into()))?;
+        self.frames.insert(to, frames);
+        if self.start_state == from {
+            self.start_state = to;
+        }
+        self.changes.push(StateChange::Renamed { from, to });
+        Ok(())
+    }
+
+    /// Changes the frames of a single state in place.
+    pub fn modify_state(
+        &mut self,
+        key: &str,
+        modify: impl FnOnce(&mut AnimationFrames),
+    ) -> KeyLookUpResult {
+        let (&state, frames) = self
+            .frames
+            .get_key_value_mut(key)
+            .ok_or_else(|| NotFoundError::SingleAnimation(key.into()))?;
+        let previous = frames.clone();
+        modify(Arc::make_mut(frames));
+        self.changes.push(StateChange::Modified { state, previous });
+        Ok(())
+    }
+
+    /// Snapshots of all states in arbitrary order.
+    pub fn state_infos(&self) -> impl Iterator<Item = StateInfo> + '_ {
+        self.frames
+            .iter()
+            .map(|(&name, frames)| StateInfo::new(name, frames, self.atlas()))
+    }
+
+    pub fn state_info(&self, key: &str) -> KeyLookUpResult<StateInfo> {
+        let (name, frames) = self.key_and_frames_under(key)?;
+        Ok(StateInfo::new(name, &frames, self.atlas()))
+    }
+
+    pub fn info(&self) -> CollectionInfo {
+        CollectionInfo::new(None, self)
+    }
+
+    pub fn has_pending_changes(&self) -> bool {
+        !self.changes.is_empty()
+    }
+
+    pub(crate) fn take_changes(&mut self) -> Vec<StateChange> {
+        std::mem::take(&mut self.changes)
     }
 
     pub fn start_state(&self) -> &'static str {
         self.start_state
     }
 
+    pub fn time_scale(&self) -> PosScaleFactor {
+        self.time_scale
+    }
+
+    /// # Errors
+    /// Returns an error if there is no direction set under `name`.
+    pub fn direction_set(&self, name: &str) -> KeyLookUpResult<&DirectionSet> {
+        self.direction_sets
+            .get(name)
+            .ok_or_else(|| NotFoundError::DirectionSet(name.to_string()))
+    }
+
+    pub fn direction_sets(&self) -> impl Iterator<Item = (&'static str, &DirectionSet)> {
+        self.direction_sets.iter().map(|(name, set)| (*name, set))
+    }
+
+    /// Adds a direction set or replaces the one with the same name.
+    /// Its members are not checked against the states of this collection.
+    pub fn insert_direction_set<'a>(
+        &mut self,
+        name: impl Into<TextLike<'a>>,
+        set: DirectionSet,
+    ) -> Option<DirectionSet> {
+        self.direction_sets
+            .insert(name.into().into_registered_name(), set)
+    }
+
+    /// # Errors
+    /// Returns an error if there is no blend set under `name`.
+    pub fn blend_set(&self, name: &str) -> KeyLookUpResult<&BlendSet> {
+        self.blend_sets
+            .get(name)
+            .ok_or_else(|| NotFoundError::BlendSet(name.to_string()))
+    }
+
+    pub fn blend_sets(&self) -> impl Iterator<Item = (&'static str, &BlendSet)> {
+        self.blend_sets.iter().map(|(name, set)| (*name, set))
+    }
+
+    /// Adds a blend set or replaces the one with the same name.
+    /// Its states are not checked against the states of this collection.
+    pub fn insert_blend_set<'a>(
+        &mut self,
+        name: impl Into<TextLike<'a>>,
+        set: BlendSet,
+    ) -> Option<BlendSet> {
+        self.blend_sets
+            .insert(name.into().into_registered_name(), set)
+    }
+
+    /// Scales the animation speed of all entities playing this collection.
+    pub fn set_time_scale(&mut self, time_scale: PosScaleFactor) {
+        self.time_scale = time_scale;
+    }
+
+    /// # Errors
+    /// Returns an error if there is no state under `key`.
+    pub fn set_start_state<'a>(&mut self, key: impl Into<TextLike<'a>>) -> KeyLookUpResult {
+        let key = key.into();
+        let (start_state, _) = self.key_and_frames_under(key.as_str())?;
+        self.start_state = start_state;
+        Ok(())
+    }
+
     pub(crate) fn key_and_frames_under(
         &self,
         key: &str,
@@ -75,6 +341,8 @@ impl AnimationCollection {
 pub struct AnimationCollectionBuilder<'a> {
     meta: AnimationAltlas,
     frames: AnimationSequenceBuilder<'a>,
+    direction_sets: HashMap<&'static str, DirectionSet>,
+    blend_sets: HashMap<&'static str, BlendSet>,
 }
 
 impl<'a> AnimationCollectionBuilder<'a> {
@@ -82,6 +350,8 @@ impl<'a> AnimationCollectionBuilder<'a> {
         Self {
             meta,
             frames: Default::default(),
+            direction_sets: Default::default(),
+            blend_sets: Default::default(),
         }
     }
     pub fn add_animation(mut self, key: impl Into<TextLike<'a>>, frames: AnimationFrames) -> Self {
@@ -100,11 +370,25 @@ impl<'a> AnimationCollectionBuilder<'a> {
             .add_row_ani(key, row, time, &self.meta, infinite);
         self
     }
+    pub fn add_direction_set(mut self, name: impl Into<TextLike<'a>>, set: DirectionSet) -> Self {
+        self.direction_sets
+            .insert(name.into().into_registered_name(), set);
+        self
+    }
+    pub fn add_blend_set(mut self, name: impl Into<TextLike<'a>>, set: BlendSet) -> Self {
+        self.blend_sets
+            .insert(name.into().into_registered_name(), set);
+        self
+    }
     pub fn build(self, start_state: impl Into<TextLike<'a>>) -> AnimationCollection {
         AnimationCollection {
             meta: self.meta,
             start_state: start_state.into().into_registered_name(),
             frames: self.frames.build(),
+            time_scale: Default::default(),
+            direction_sets: self.direction_sets,
+            blend_sets: self.blend_sets,
+            changes: Vec::new(),
         }
     }
 }
@@ -134,3 +418,102 @@ impl<'a> AnimationSequenceBuilder<'a> {
         types::to_build_to_ani_seq(self.0)
     }
 }
+
+#[cfg(test)]
+mod testing {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::prelude::AnimationAltlasMeta;
+
+    fn collection() -> AnimationCollection {
+        let meta = AnimationAltlasMeta::new(4, 6, Vec2::splat(64.))
+            .build(Handle::default(), &mut Assets::<TextureAtlas>::default());
+        let time = AnimationDuration::from_secs(1);
+        AnimationCollectionBuilder::new(meta)
+            .add_row_ani("Left", 0, time, true)
+            .add_row_ani("Right", 1, time, true)
+            .build("Left")
+    }
+
+    #[test]
+    fn records_single_state_changes() {
+        let mut collection = collection();
+        collection
+            .modify_state("Right", |frames| frames.set_infinite(false))
+            .unwrap();
+        assert!(matches!(
+            collection.rename_state("Right", "Left"),
+            Err(AnimationError::DuplicateState(state)) if state == "Left"
+        ));
+        collection.rename_state("Right", "Up").unwrap();
+        assert!(collection.remove_state("Left").is_err());
+        collection.remove_state("Up").unwrap();
+
+        let changes = collection.take_changes();
+        assert!(matches!(
+            changes.as_slice(),
+            [
+                StateChange::Modified { state: "Right", .. },
+                StateChange::Renamed {
+                    from: "Right",
+                    to: "Up"
+                },
+                StateChange::Removed("Up"),
+            ]
+        ));
+        assert!(!collection.has_pending_changes());
+    }
+
+    #[test]
+    fn set_frames_records_differences() {
+        let mut collection = collection();
+        let time = AnimationDuration::from_secs(1);
+        let new_seq = AnimationSequenceBuilder::default()
+            .add_animation("Left", AnimationFrames::from_row(0, time, 6, true).unwrap())
+            .add_animation("Top", AnimationFrames::from_row(2, time, 6, true).unwrap())
+            .build();
+        collection.set_frames(new_seq);
+
+        let changes = collection.take_changes();
+        assert_eq!(2, changes.len());
+        assert!(changes
+            .iter()
+            .any(|change| matches!(change, StateChange::Added("Top"))));
+        assert!(changes
+            .iter()
+            .any(|change| matches!(change, StateChange::Removed("Right"))));
+    }
+
+    #[test]
+    fn replaces_only_states_of_one_source() {
+        let mut collection = collection();
+        let time = AnimationDuration::from_secs(2);
+        let combat = AnimationSequenceBuilder::default()
+            .add_animation(
+                "Attack",
+                AnimationFrames::from_row(2, time, 6, false).unwrap(),
+            )
+            .build();
+        collection.merge(AnimationCollection {
+            frames: combat,
+            ..self::collection()
+        });
+        let reloaded = AnimationSequenceBuilder::default()
+            .add_animation(
+                "Block",
+                AnimationFrames::from_row(3, time, 6, false).unwrap(),
+            )
+            .build();
+        collection.replace_states(&["Attack"], reloaded);
+
+        let mut states: Vec<&str> = collection.frames().keys().copied().collect();
+        states.sort_unstable();
+        assert_eq!(vec!["Block", "Left", "Right"], states);
+
+        collection.replace_states(&["Left", "Block"], AnimationSequence::default());
+        let mut states: Vec<&str> = collection.frames().keys().copied().collect();
+        states.sort_unstable();
+        assert_eq!(vec!["Left", "Right"], states);
+    }
+}
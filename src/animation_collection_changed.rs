@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+use crate::{
+    collection_look_up::CollectionId,
+    types::{AnimationReference, ImmutableAnimationFrames},
+};
+
+/// Single change of an animation state inside a collection.
+#[derive(Debug, Clone)]
+pub enum StateChange {
+    Added(&'static str),
+    Removed(&'static str),
+    Renamed {
+        from: &'static str,
+        to: &'static str,
+    },
+    /// Frames of a state were replaced. Contains the frames before the change.
+    Modified {
+        state: &'static str,
+        previous: ImmutableAnimationFrames,
+    },
+}
+
+impl StateChange {
+    pub fn state(&self) -> &'static str {
+        match self {
+            StateChange::Added(state)
+            | StateChange::Removed(state)
+            | StateChange::Renamed { to: state, .. }
+            | StateChange::Modified { state, .. } => state,
+        }
+    }
+}
+
+/// Is sent after the states of a collection were changed at runtime.
+/// Entities playing an affected state are already updated at this point.
+#[derive(Event, Debug, Clone)]
+pub struct AnimationCollectionChanged {
+    pub collection: CollectionId,
+    pub changes: Vec<StateChange>,
+}
+
+/// Is sent for an entity whose current state was removed from its collection,
+/// for example by a hot reload. The entity plays the start state of the collection afterwards.
+#[derive(Event, Debug, Clone)]
+pub struct AnimationFellBackToStart {
+    pub who: Entity,
+    pub collection: CollectionId,
+    pub removed_state: AnimationReference,
+    pub start_state: &'static str,
+}
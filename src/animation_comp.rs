@@ -1,14 +1,25 @@
 use std::borrow::Cow;
 
-use bevy::prelude::*;
+use bevy::{prelude::*, sprite::Anchor};
 #[cfg(feature = "bevy_inspect")]
 use bevy_inspector_egui::prelude::*;
 
 use crate::{
-    animation_error::NotFoundError,
+    animation_collection::AnimationCollection,
+    animation_collection_changed::StateChange,
+    animation_error::{AnimationError, NotFoundError},
+    animation_frames::AnimationFrames,
+    animation_time_factor::AnimationTimeScale,
+    collection_look_up::{not_found_asset, CollectionId, CollectionLookUp},
+    end_behavior::EndBehavior,
+    frame_tags::ActiveTags,
+    frame_target::FrameTarget,
     prelude::{AllAnimationResource, ImmutableAnimationFrames},
     text_like::TextLike,
-    types::{AnimationDuration, AnimationIndex, AnimationReference, KeyLookUpResult},
+    types::{
+        AnimationDuration, AnimationIndex, AnimationReference, AnimationResult, KeyLookUpResult,
+    },
+    PercentScaleFactor, PosScaleFactor,
 };
 
 #[derive(Component, Debug, Reflect)]
@@ -20,12 +31,40 @@ use crate::{
 )]
 pub struct AnimationComp {
     pub(crate) sequence: AnimationReference,
+    /// If set then the animation states are taken from this asset
+    /// instead of the key `sequence` inside [`AllAnimationResource`].
+    pub(crate) collection: Option<Handle<AnimationCollection>>,
     pub(crate) current_state: AnimationReference,
     pub(crate) has_reached_end_without_repeat: bool,
+    /// Whether the current state had ended when the next state was set.
+    /// Leaving an ended state does not count as an interruption.
+    pub(crate) ended_before_switch: bool,
+    /// Set once the last frame of a state without repeat has ended
+    /// until its [`EndBehavior`] is applied.
+    pub(crate) pending_end: bool,
+    /// Overrides the [`EndBehavior`] of every state.
+    pub(crate) end_behavior: Option<EndBehavior>,
+    /// Completed loops of the current state.
+    pub(crate) loop_count: u32,
     pub(crate) reset_state: bool,
     pub(crate) duration_for_animation: Timer,
     #[reflect(ignore)]
     pub(crate) next_state: Option<AnimationReference>,
+    /// The next state starts at the normalized phase of the current state instead of its start.
+    pub(crate) keep_phase: bool,
+    /// Speed picked by [`Self::set_blend_parameter`].
+    /// Applies on top of the [`AnimationTimeScale`] of the entity.
+    pub(crate) blend_scale: PosScaleFactor,
+    /// Anchor of the frame target before the anchor of a state was applied.
+    /// Is restored for states without anchors.
+    #[reflect(ignore)]
+    pub(crate) base_anchor: Option<Anchor>,
+    #[reflect(ignore)]
+    pub(crate) active_tags: Option<ActiveTags>,
+    /// Frames the last updates moved over without stopping on them.
+    /// Is only recorded for states with tags, so tags of skipped frames are still reported.
+    #[reflect(ignore)]
+    pub(crate) skipped_frames: Vec<AnimationIndex>,
 }
 
 impl AnimationComp {
@@ -36,21 +75,70 @@ impl AnimationComp {
     ) -> Result<Self, NotFoundError> {
         let (all_frames, start_state) = (all_frames.into(), start_state.into());
         let (_, frames) = get_animation_seq(repos, &all_frames, &start_state)?;
+        Ok(Self::with_frames(all_frames, None, start_state, &frames))
+    }
+
+    /// Creates a component which takes its animation states from a loaded collection asset.
+    /// There is no asset loader for [`AnimationCollection`] yet. The asset has to be added
+    /// via [`Assets::add`], for example from `AnimationAssets::to_animaton_collection`.
+    /// Hot reloads of the `.animations.ron` file do not reach entities using the asset.
+    /// # Errors
+    /// Returns an error if the asset is not loaded or has no state named `start_state`.
+    pub fn from_asset(
+        collection: Handle<AnimationCollection>,
+        start_state: impl Into<Cow<'static, str>>,
+        assets: &Assets<AnimationCollection>,
+    ) -> Result<Self, NotFoundError> {
+        let start_state = start_state.into();
+        let frames = assets
+            .get(&collection)
+            .ok_or_else(|| not_found_asset(&collection))?
+            .get_frames_under(&start_state)?;
+        Ok(Self::with_frames(
+            Cow::Borrowed(""),
+            Some(collection),
+            start_state,
+            &frames,
+        ))
+    }
+
+    fn with_frames(
+        sequence: AnimationReference,
+        collection: Option<Handle<AnimationCollection>>,
+        start_state: AnimationReference,
+        frames: &ImmutableAnimationFrames,
+    ) -> Self {
         let duration_secs = frames.time_per_frame();
         let frame_seq_duration = new_reapting_time(duration_secs);
-        Ok(Self {
+        Self {
             duration_for_animation: frame_seq_duration,
-            sequence: all_frames,
+            sequence,
+            collection,
             current_state: start_state,
             next_state: None,
+            keep_phase: false,
+            blend_scale: PosScaleFactor::default(),
+            base_anchor: None,
+            active_tags: None,
+            skipped_frames: Vec::new(),
             reset_state: false,
             has_reached_end_without_repeat: false,
-        })
+            ended_before_switch: false,
+            pending_end: false,
+            end_behavior: None,
+            loop_count: 0,
+        }
     }
 
     pub fn set_state<'a>(&mut self, key: impl Into<TextLike<'a>>) {
         let key = key.into();
+        if self.next_state.is_none() {
+            self.ended_before_switch = self.has_reached_end_without_repeat;
+        }
         self.has_reached_end_without_repeat = false;
+        self.pending_end = false;
+        self.keep_phase = false;
+        self.blend_scale = PosScaleFactor::default();
         self.next_state = Some(key.into());
     }
 
@@ -63,25 +151,55 @@ impl AnimationComp {
 
     pub fn reset_current_state(&mut self) {
         self.has_reached_end_without_repeat = false;
+        self.pending_end = false;
         self.reset_state = true;
     }
 
-    pub fn start_index(&self, repos: &AllAnimationResource) -> KeyLookUpResult<AnimationIndex> {
+    pub fn start_index(&self, repos: &impl CollectionLookUp) -> KeyLookUpResult<AnimationIndex> {
         let animation = self.get_current_seq(repos)?;
         Ok(animation.start())
     }
 
+    pub fn get_collection<'a>(
+        &self,
+        repos: &'a impl CollectionLookUp,
+    ) -> KeyLookUpResult<&'a AnimationCollection> {
+        repos.collection_for(self)
+    }
+
     pub fn get_current_seq(
         &self,
-        repos: &AllAnimationResource,
+        repos: &impl CollectionLookUp,
     ) -> KeyLookUpResult<ImmutableAnimationFrames> {
-        get_animation_seq(repos, &self.sequence, &self.current_state).map(|(_, value)| value)
+        self.get_collection(repos)?
+            .get_frames_under(&self.current_state)
     }
 
+    /// Key of the collection inside [`AllAnimationResource`].
+    /// Is empty if the component refers to a collection asset.
     pub fn sequence(&self) -> &str {
         self.sequence.as_ref()
     }
 
+    pub fn collection_handle(&self) -> Option<&Handle<AnimationCollection>> {
+        self.collection.as_ref()
+    }
+
+    pub fn collection_id(&self) -> CollectionId {
+        match &self.collection {
+            Some(handle) => CollectionId::Asset(handle.id()),
+            None => CollectionId::Key(TextLike::Ref(self.sequence.as_ref()).into_registered_name()),
+        }
+    }
+
+    pub(crate) fn refers_to(&self, collection: &CollectionId) -> bool {
+        match (collection, &self.collection) {
+            (CollectionId::Asset(id), Some(handle)) => handle.id() == *id,
+            (CollectionId::Key(key), None) => *key == self.sequence.as_ref(),
+            _ => false,
+        }
+    }
+
     pub fn current_state(&self) -> &str {
         self.current_state.as_ref()
     }
@@ -93,6 +211,408 @@ impl AnimationComp {
     pub fn get_reset_state(&self) -> bool {
         self.reset_state
     }
+
+    /// Completed loops of the current state. A finished state without repeat counts as one loop.
+    pub fn loop_count(&self) -> u32 {
+        self.loop_count
+    }
+
+    /// Switches to the member of the direction set `set` which fits `direction` best.
+    /// The current member is kept around the border between two members to avoid flickering.
+    /// A zero vector keeps the current state.
+    /// # Errors
+    /// Returns an error if the collection has no direction set under `set`.
+    pub fn change_direction(
+        &mut self,
+        set: &str,
+        direction: Vec2,
+        repos: &impl CollectionLookUp,
+    ) -> KeyLookUpResult<()> {
+        let current = self.next_state.as_deref().unwrap_or(&self.current_state);
+        let picked = self
+            .get_collection(repos)?
+            .direction_set(set)?
+            .pick(direction, current);
+        match picked {
+            Some(state) if state == self.current_state => self.next_state = None,
+            Some(state) => self.change_state(state),
+            None => (),
+        }
+        Ok(())
+    }
+
+    /// Switches to the state of the range of the blend set `set` which contains `parameter`.
+    /// A new state continues at the normalized phase of the previous one.
+    /// The state plays at the speed which matches `parameter` with the reference of the range
+    /// on top of the [`AnimationTimeScale`], and at the normal speed for a range without reference.
+    /// Switching to another state via [`Self::set_state`] goes back to the normal speed.
+    /// # Errors
+    /// Returns an error if the collection has no blend set under `set`
+    /// or if `parameter` is not a finite number.
+    pub fn set_blend_parameter(
+        &mut self,
+        set: &str,
+        parameter: f32,
+        repos: &impl CollectionLookUp,
+    ) -> AnimationResult {
+        if !parameter.is_finite() {
+            return Err(AnimationError::InvalidBlendParameter(parameter));
+        }
+        let Some(range) = self
+            .get_collection(repos)?
+            .blend_set(set)?
+            .select(parameter)
+        else {
+            return Ok(());
+        };
+        let scale = range.time_scale(parameter);
+        if !scale.to_f32().is_finite() {
+            return Err(AnimationError::InvalidBlendParameter(parameter));
+        }
+        if range.state == self.current_state {
+            self.next_state = None;
+        } else if self.next_state.as_deref() != Some(range.state) {
+            self.set_state(range.state);
+            self.keep_phase = true;
+        }
+        self.blend_scale = scale;
+        Ok(())
+    }
+
+    /// Overrides the [`EndBehavior`] of the states for this entity.
+    /// `None` uses the end behavior of the current state.
+    pub fn set_end_behavior(&mut self, end_behavior: Option<EndBehavior>) {
+        self.end_behavior = end_behavior;
+    }
+
+    pub fn with_end_behavior(mut self, end_behavior: EndBehavior) -> Self {
+        self.end_behavior = Some(end_behavior);
+        self
+    }
+
+    /// End behavior which is applied after the current state ended.
+    /// # Errors
+    /// Returns an error if the current state is not found in the collection.
+    pub fn end_behavior(&self, repos: &impl CollectionLookUp) -> KeyLookUpResult<EndBehavior> {
+        match self.end_behavior {
+            Some(end_behavior) => Ok(end_behavior),
+            None => Ok(self.get_current_seq(repos)?.end_behavior()),
+        }
+    }
+
+    /// Jumps to the frame `offset` counted from the first frame of the current state.
+    /// An offset past the last frame is clamped to it.
+    /// # Errors
+    /// Returns an error if the current state is not found in the collection.
+    pub fn seek_to_frame(
+        &mut self,
+        target: &mut impl FrameTarget,
+        offset: usize,
+        repos: &impl CollectionLookUp,
+    ) -> KeyLookUpResult<()> {
+        let frames = self.get_current_seq(repos)?;
+        let offset = offset.min(frames.frame_gap()) as u32;
+        self.seek(
+            target.index_mut(),
+            &frames,
+            frames.time_per_frame() * offset,
+        );
+        Ok(())
+    }
+
+    /// Jumps to the point in time of the current state.
+    /// `time` is measured like [`Self::elapsed`], so with the time scales applied.
+    /// # Errors
+    /// Returns an error if the current state is not found in the collection.
+    pub fn seek_to_time(
+        &mut self,
+        target: &mut impl FrameTarget,
+        time: AnimationDuration,
+        time_scale: &AnimationTimeScale,
+        repos: &impl CollectionLookUp,
+    ) -> KeyLookUpResult<()> {
+        let collection = self.get_collection(repos)?;
+        let frames = collection.get_frames_under(&self.current_state)?;
+        let time_scale = self.combined_scale(collection, time_scale);
+        self.seek(target.index_mut(), &frames, time_scale.scale_duration(time));
+        Ok(())
+    }
+
+    /// Jumps to the normalized position of the current state.
+    /// Zero is the start of the first frame and one the end of the last frame.
+    /// # Errors
+    /// Returns an error if the current state is not found in the collection.
+    pub fn seek_to_precent(
+        &mut self,
+        target: &mut impl FrameTarget,
+        precent: PercentScaleFactor,
+        repos: &impl CollectionLookUp,
+    ) -> KeyLookUpResult<()> {
+        let frames = self.get_current_seq(repos)?;
+        let time = frames.total_time().mul_f64(precent.to_f32().into());
+        self.seek(target.index_mut(), &frames, time);
+        Ok(())
+    }
+
+    /// Time the current state has been playing at the current speed.
+    /// # Errors
+    /// Returns an error if the current state is not found in the collection.
+    pub fn elapsed(
+        &self,
+        target: &impl FrameTarget,
+        time_scale: &AnimationTimeScale,
+        repos: &impl CollectionLookUp,
+    ) -> KeyLookUpResult<AnimationDuration> {
+        let collection = self.get_collection(repos)?;
+        let frames = collection.get_frames_under(&self.current_state)?;
+        let elapsed = self.elapsed_in_state(target.index(), &frames);
+        Ok(self
+            .combined_scale(collection, time_scale)
+            .real_duration(elapsed))
+    }
+
+    /// Time until the last frame of the current state ends at the current speed.
+    /// # Errors
+    /// Returns an error if the current state is not found in the collection.
+    pub fn remaining(
+        &self,
+        target: &impl FrameTarget,
+        time_scale: &AnimationTimeScale,
+        repos: &impl CollectionLookUp,
+    ) -> KeyLookUpResult<AnimationDuration> {
+        let collection = self.get_collection(repos)?;
+        let frames = collection.get_frames_under(&self.current_state)?;
+        let remaining = frames
+            .total_time()
+            .saturating_sub(self.elapsed_in_state(target.index(), &frames));
+        Ok(self
+            .combined_scale(collection, time_scale)
+            .real_duration(remaining))
+    }
+
+    /// Time all frames of the current state take at the current speed.
+    /// # Errors
+    /// Returns an error if the current state is not found in the collection.
+    pub fn total_duration(
+        &self,
+        time_scale: &AnimationTimeScale,
+        repos: &impl CollectionLookUp,
+    ) -> KeyLookUpResult<AnimationDuration> {
+        let collection = self.get_collection(repos)?;
+        let frames = collection.get_frames_under(&self.current_state)?;
+        Ok(self
+            .combined_scale(collection, time_scale)
+            .real_duration(frames.total_time()))
+    }
+
+    /// Speed of the current state with the time scales of the entity, its collection
+    /// and its blend parameter applied.
+    pub(crate) fn combined_scale(
+        &self,
+        collection: &AnimationCollection,
+        time_scale: &AnimationTimeScale,
+    ) -> AnimationTimeScale {
+        AnimationTimeScale(
+            time_scale.0 * collection.time_scale().to_f32() * self.blend_scale.to_f32(),
+        )
+    }
+
+    /// Returns true if the frame of `target` is inside the tag `tag` of the current state.
+    /// # Errors
+    /// Returns an error if the current state is not found in the collection.
+    pub fn is_in_tag(
+        &self,
+        tag: &str,
+        target: &impl FrameTarget,
+        repos: &impl CollectionLookUp,
+    ) -> KeyLookUpResult<bool> {
+        Ok(self.get_current_seq(repos)?.is_in_tag(tag, target.index()))
+    }
+
+    /// Names of all tags of the current state containing the frame of `target`.
+    /// # Errors
+    /// Returns an error if the current state is not found in the collection.
+    pub fn current_tags(
+        &self,
+        target: &impl FrameTarget,
+        repos: &impl CollectionLookUp,
+    ) -> KeyLookUpResult<Vec<&'static str>> {
+        Ok(self
+            .get_current_seq(repos)?
+            .tags_at(target.index())
+            .collect())
+    }
+
+    /// Sets the anchor of `target` to the one of its frame inside `frames`.
+    pub(crate) fn apply_anchor(&mut self, target: &mut impl FrameTarget, frames: &AnimationFrames) {
+        let Some(current) = target.anchor() else {
+            return;
+        };
+        match frames.anchor_at(target.index()) {
+            Some(anchor) => {
+                self.base_anchor.get_or_insert(current);
+                if current.as_vec() != anchor {
+                    target.set_anchor(Anchor::Custom(anchor));
+                }
+            }
+            None => {
+                if let Some(base_anchor) = self.base_anchor.take() {
+                    target.set_anchor(base_anchor);
+                }
+            }
+        }
+    }
+
+    /// Played part of `frames` from zero to one.
+    pub(crate) fn phase(&self, index: AnimationIndex, frames: &AnimationFrames) -> f64 {
+        if frames.total_time().is_zero() {
+            return 0.;
+        }
+        let elapsed = self.elapsed_in_state(index, frames).as_secs_f64();
+        (elapsed / frames.total_time().as_secs_f64()).clamp(0., 1.)
+    }
+
+    fn elapsed_in_state(
+        &self,
+        index: AnimationIndex,
+        frames: &AnimationFrames,
+    ) -> AnimationDuration {
+        if self.has_reached_end_without_repeat {
+            return frames.total_time();
+        }
+        let offset = index.saturating_sub(frames.start()).min(frames.frame_gap()) as u32;
+        frames.time_per_frame() * offset + self.duration_for_animation.elapsed()
+    }
+
+    /// Moves `index` and the frame timer to `time` inside `frames`.
+    pub(crate) fn seek(
+        &mut self,
+        index: &mut AnimationIndex,
+        frames: &AnimationFrames,
+        time: AnimationDuration,
+    ) {
+        let per_frame = frames.time_per_frame();
+        let offset = match per_frame.as_nanos() {
+            0 => 0,
+            nanos => (time.as_nanos() / nanos) as usize,
+        }
+        .min(frames.frame_gap());
+        let rest = time
+            .saturating_sub(per_frame * offset as u32)
+            .min(per_frame);
+
+        *index = frames.start() + offset;
+        self.duration_for_animation.set_duration(per_frame);
+        self.duration_for_animation.set_elapsed(rest);
+        self.has_reached_end_without_repeat = false;
+    }
+
+    /// Advances the current state by `delta` which is already scaled.
+    /// Moves as many frames as have elapsed and carries the remaining time over to the next call.
+    /// Returns how often the last frame of `frames` was finished.
+    pub(crate) fn advance(
+        &mut self,
+        index: &mut AnimationIndex,
+        frames: &AnimationFrames,
+        delta: AnimationDuration,
+    ) -> usize {
+        // The current state is frozen until a pending switch to the next state is applied.
+        if self.has_reached_end_without_repeat || self.next_state.is_some() {
+            return 0;
+        }
+        let timer = self.duration_for_animation.tick(delta);
+        let steps = match timer.times_finished_this_tick() as usize {
+            // A state without duration moves one frame per update.
+            steps if timer.duration().is_zero() => steps.min(1),
+            steps => steps,
+        };
+        if steps == 0 {
+            return 0;
+        }
+
+        let position = index.saturating_sub(frames.start()).min(frames.frame_gap());
+        if frames.has_tags() {
+            let passed = (position + 1..position + steps).map(|moved| moved % frames.frame_count());
+            let passed = passed.take_while(|offset| frames.is_infinite() || *offset != 0);
+            self.skipped_frames
+                .extend(passed.map(|offset| frames.start() + offset));
+        }
+        if !frames.is_infinite() {
+            let until_end = frames.frame_gap() - position;
+            if steps > until_end {
+                *index = frames.end();
+                self.has_reached_end_without_repeat = true;
+                self.pending_end = true;
+                self.loop_count = self.loop_count.saturating_add(1);
+                return 1;
+            }
+            *index = frames.start() + position + steps;
+            return 0;
+        }
+
+        let moved = position + steps;
+        *index = frames.start() + moved % frames.frame_count();
+        let loops = moved / frames.frame_count();
+        self.loop_count = self.loop_count.saturating_add(loops as u32);
+        loops
+    }
+
+    /// Updates the current state after its collection changed.
+    /// Keeps the progress within a modified state and clamps `index` to its frames.
+    /// Falls back to the start state if the current state was removed.
+    /// Returns true if the fall back happened.
+    pub(crate) fn apply_collection_changes(
+        &mut self,
+        index: &mut AnimationIndex,
+        changes: &[StateChange],
+        collection: &AnimationCollection,
+    ) -> KeyLookUpResult<bool> {
+        let mut previous_frames = None;
+        for change in changes {
+            match change {
+                StateChange::Renamed { from, to } => {
+                    if self.current_state == *from {
+                        self.current_state = Cow::Borrowed(to);
+                    }
+                    if self.next_state.as_deref() == Some(*from) {
+                        self.next_state = Some(Cow::Borrowed(to));
+                    }
+                }
+                StateChange::Modified { state, previous } if self.current_state == *state => {
+                    previous_frames.get_or_insert_with(|| previous.clone());
+                }
+                _ => (),
+            }
+        }
+        if let Some(next) = self.next_state.as_deref() {
+            if collection.get_frames_under(next).is_err() {
+                self.next_state = None;
+            }
+        }
+
+        let Ok(frames) = collection.get_frames_under(&self.current_state) else {
+            let start_state = collection.start_state();
+            let frames = collection.get_frames_under(start_state)?;
+            self.current_state = Cow::Borrowed(start_state);
+            self.duration_for_animation = new_reapting_time(frames.time_per_frame());
+            self.has_reached_end_without_repeat = false;
+            self.loop_count = 0;
+            *index = frames.start();
+            return Ok(true);
+        };
+
+        if let Some(previous) = previous_frames {
+            let progress = previous.precent(*index);
+            *index = frames.index_from_precent(progress);
+            self.duration_for_animation
+                .set_duration(frames.time_per_frame());
+        }
+        *index = (*index).clamp(frames.start(), frames.end());
+        if *index != frames.end() || frames.is_infinite() {
+            self.has_reached_end_without_repeat = false;
+        }
+        Ok(false)
+    }
 }
 
 pub(crate) fn new_reapting_time(time: AnimationDuration) -> Timer {
@@ -108,3 +628,211 @@ pub(crate) fn get_animation_seq(
         .animation_under(frames)?
         .key_and_frames_under(current_state)
 }
+
+#[cfg(test)]
+mod testing {
+    use std::sync::Arc;
+
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{prelude::AnimationCollectionBuilder, test_app, BlendSet};
+
+    const FRAME_COUNT: usize = 4;
+
+    fn frames(infinite: bool) -> AnimationFrames {
+        AnimationFrames::from_row(0, test_app::ROW_TIME, FRAME_COUNT, infinite).unwrap()
+    }
+
+    fn animator(frames: &AnimationFrames) -> AnimationComp {
+        AnimationComp::with_frames(
+            Cow::Borrowed("Test"),
+            None,
+            Cow::Borrowed("Idle"),
+            &Arc::new(frames.clone()),
+        )
+    }
+
+    #[test]
+    fn keeps_exact_time_over_long_run() {
+        let frames = frames(true);
+        let mut animator = animator(&frames);
+        let mut index = frames.start();
+        let mut ended = 0;
+        // 10 seconds with uneven updates.
+        for delta_ms in [7, 33, 10].repeat(200) {
+            ended += animator.advance(
+                &mut index,
+                &frames,
+                AnimationDuration::from_millis(delta_ms),
+            );
+        }
+        assert_eq!(10, ended);
+        assert_eq!(frames.start(), index);
+        assert!(animator.duration_for_animation.elapsed().is_zero());
+
+        // One update per 1/64 second is exact in nanoseconds.
+        for _ in 0..64 * 5 + 16 {
+            ended += animator.advance(
+                &mut index,
+                &frames,
+                AnimationDuration::from_nanos(15_625_000),
+            );
+        }
+        assert_eq!(15, ended);
+        assert_eq!(frames.start() + 1, index);
+    }
+
+    #[test]
+    fn catches_up_several_frames_in_one_update() {
+        let frames = frames(true);
+        let mut animator = animator(&frames);
+        let mut index = frames.start();
+
+        let ended = animator.advance(&mut index, &frames, AnimationDuration::from_millis(600));
+        assert_eq!((0, 2), (ended, index));
+        let ended = animator.advance(&mut index, &frames, AnimationDuration::from_millis(150));
+        assert_eq!((0, 3), (ended, index));
+        let ended = animator.advance(&mut index, &frames, AnimationDuration::from_millis(2_250));
+        assert_eq!((3, 0), (ended, index));
+    }
+
+    #[test]
+    fn stops_at_last_frame_without_repeat() {
+        let frames = frames(false);
+        let mut animator = animator(&frames);
+        let mut index = frames.start();
+
+        let ended = animator.advance(&mut index, &frames, AnimationDuration::from_millis(999));
+        assert_eq!((0, frames.end()), (ended, index));
+        let ended = animator.advance(&mut index, &frames, AnimationDuration::from_secs(5));
+        assert_eq!((1, frames.end()), (ended, index));
+        assert!(animator.has_reached_end_without_repeat);
+        let ended = animator.advance(&mut index, &frames, AnimationDuration::from_secs(5));
+        assert_eq!((0, frames.end()), (ended, index));
+    }
+
+    #[test]
+    fn seeks_and_reports_timing() {
+        let frames = frames(false);
+        let mut animator = animator(&frames);
+        let mut index = frames.start();
+
+        animator.seek(&mut index, &frames, AnimationDuration::from_millis(600));
+        assert_eq!(2, index);
+        assert_eq!(
+            AnimationDuration::from_millis(600),
+            animator.elapsed_in_state(index, &frames)
+        );
+        let ended = animator.advance(&mut index, &frames, AnimationDuration::from_millis(150));
+        assert_eq!((0, 3), (ended, index));
+
+        animator.seek(&mut index, &frames, AnimationDuration::from_secs(3));
+        assert_eq!(frames.end(), index);
+        assert_eq!(
+            frames.total_time(),
+            animator.elapsed_in_state(index, &frames)
+        );
+        let slow = AnimationTimeScale(PosScaleFactor::new(0.5).unwrap());
+        assert_eq!(
+            AnimationDuration::from_secs(2),
+            slow.real_duration(frames.total_time())
+        );
+    }
+
+    #[test]
+    fn saturates_real_duration_for_tiny_scale() {
+        let frames = frames(false);
+        let tiny = AnimationTimeScale(PosScaleFactor::new(1e-25).unwrap());
+        assert_eq!(Duration::MAX, tiny.real_duration(frames.total_time()));
+        let stopped = AnimationTimeScale(PosScaleFactor::new(0.).unwrap());
+        assert_eq!(Duration::MAX, stopped.real_duration(frames.total_time()));
+        assert_eq!(Duration::ZERO, tiny.real_duration(Duration::ZERO));
+    }
+
+    #[test]
+    fn repeats_single_frame() {
+        let frames = AnimationFrames::new(
+            0,
+            Some(2),
+            None,
+            Some(3),
+            AnimationDuration::from_millis(100),
+            FRAME_COUNT,
+            true,
+        )
+        .unwrap();
+        assert_eq!(1, frames.frame_count());
+        let mut animator = animator(&frames);
+        let mut index = frames.start();
+
+        let ended = animator.advance(&mut index, &frames, AnimationDuration::from_millis(350));
+        assert_eq!((3, frames.start()), (ended, index));
+    }
+
+    #[test]
+    fn blends_with_kept_phase_and_own_time_scale() {
+        let mut app = test_app::app();
+        let time = test_app::ROW_TIME;
+        let moving = BlendSet::default()
+            .with_range("Walk", 0., Some(100.))
+            .with_range("Run", 200., Some(300.));
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_row_ani("Walk", 1, time, true)
+            .add_row_ani("Run", 2, time, true)
+            .add_blend_set("move", moving)
+            .build("Walk");
+        test_app::add_collection(&mut app, collection);
+        let sprite = test_app::spawn_sprite(&mut app);
+        let double = PosScaleFactor::new(2.).unwrap();
+        app.world
+            .entity_mut(sprite)
+            .insert(AnimationTimeScale(double));
+        test_app::tick(&mut app, Duration::ZERO);
+        test_app::tick(&mut app, AnimationDuration::from_millis(250));
+        let row = |row: usize| row * test_app::COLUMNS;
+        let index = |app: &App| test_app::index(app, sprite);
+        assert_eq!(row(1) + 2, index(&app));
+
+        let set_parameter = |app: &mut App, parameter: f32| {
+            app.world
+                .resource_scope(|world, repos: Mut<AllAnimationResource>| {
+                    let mut animator = world.get_mut::<AnimationComp>(sprite).unwrap();
+                    animator.set_blend_parameter("move", parameter, &*repos)
+                })
+        };
+        set_parameter(&mut app, 50.).unwrap();
+        let animator = app.world.get::<AnimationComp>(sprite).unwrap();
+        assert_eq!(0.5, animator.blend_scale.to_f32());
+        let repos = app.world.resource::<AllAnimationResource>();
+        let total = animator.total_duration(&AnimationTimeScale(double), repos);
+        assert_eq!(AnimationDuration::from_secs(1), total.unwrap());
+
+        // Run starts at the phase of Walk and plays at 2 x 2 the speed.
+        set_parameter(&mut app, 600.).unwrap();
+        test_app::tick(&mut app, Duration::ZERO);
+        assert_eq!(row(2) + 2, index(&app));
+        test_app::tick(&mut app, AnimationDuration::from_millis(125));
+        assert_eq!(row(2), index(&app));
+        assert_eq!(
+            double,
+            app.world.get::<AnimationTimeScale>(sprite).unwrap().0
+        );
+
+        for invalid in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert!(matches!(
+                set_parameter(&mut app, invalid),
+                Err(AnimationError::InvalidBlendParameter(_))
+            ));
+        }
+        let animator = app.world.get::<AnimationComp>(sprite).unwrap();
+        assert_eq!(
+            ("Run", 2.),
+            (animator.current_state(), animator.blend_scale.to_f32())
+        );
+
+        test_app::switch_to(&mut app, sprite, "Walk");
+        let animator = app.world.get::<AnimationComp>(sprite).unwrap();
+        assert_eq!(PosScaleFactor::default(), animator.blend_scale);
+    }
+}
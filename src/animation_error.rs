@@ -11,10 +11,22 @@ pub enum AnimationError {
     NoSeqeunceKeyProvided,
     #[error("Key {0} for an animation sequence was provided.")]
     DuplicateKeySequenceProvided(String),
+    #[error("There is already a state named {0} in this collection.")]
+    DuplicateState(String),
     #[error("{0}")]
     NegativeAnimationTime(#[from] NegativeAnimationTime),
     #[error("Animation sequence was not loade yet")]
     AnimationNotLoadedYet,
+    #[error(
+        "Animations for key {0} can not be merged because they use a different texture atlas."
+    )]
+    MergeWithDifferentAtlas(String),
+    #[error("State {0} is the start state of its collection and can not be removed.")]
+    RemovingStartState(String),
+    #[error("No image was provided for the animations under key {0}.")]
+    NoImageProvided(String),
+    #[error("Blend parameter must be a finite number. Actual: {0}")]
+    InvalidBlendParameter(f32),
 }
 
 #[derive(Debug, Error)]
@@ -27,6 +39,12 @@ pub enum NotFoundError {
     AnimationSequence(String),
     #[error("There are no animation frames for key the ({0})")]
     SingleAnimation(String),
+    #[error("There is no loaded animation collection asset for the handle ({0})")]
+    CollectionAsset(String),
+    #[error("There is no direction set for the key ({0})")]
+    DirectionSet(String),
+    #[error("There is no blend set for the key ({0})")]
+    BlendSet(String),
 }
 
 #[derive(Debug, Error)]
@@ -41,4 +59,17 @@ pub enum AnimationFrameError {
         start: AnimationIndex,
         end: AnimationIndex,
     },
+    #[error(
+        "Tag {name} covers frames {from} to {to} but its state has only {frame_count} frames."
+    )]
+    TagOutOfFrames {
+        name: String,
+        from: AnimationIndex,
+        to: AnimationIndex,
+        frame_count: usize,
+    },
+    #[error("A state made of separate images needs at least one image.")]
+    NoImages,
+    #[error("A state made of cells of the sprite sheet needs a start row.")]
+    NoStartRow,
 }
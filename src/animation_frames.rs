@@ -1,17 +1,56 @@
-use bevy::reflect::Reflect;
+use bevy::{prelude::Vec2, reflect::Reflect, utils::HashMap};
 
 use crate::{
     animation_error::AnimationFrameError,
+    end_behavior::EndBehavior,
+    frame_boxes::FrameBox,
+    frame_metadata::FrameValue,
+    frame_sockets::FrameSocket,
+    frame_tags::FrameTag,
+    frame_target::FrameTarget,
+    per_frame::PerFrame,
     types::{AnimationDuration, AnimationFrameResult, AnimationIndex},
     utils, PercentScaleFactor,
 };
 
-#[derive(Debug, Clone, Reflect)]
+/// Axes on which the sprite is flipped while a mirrored state is active.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct Mirror {
+    pub x: bool,
+    pub y: bool,
+}
+
+impl Mirror {
+    /// Sets the flips of `target` to this mirror.
+    pub fn apply(self, target: &mut impl FrameTarget) {
+        if target.flip() != self {
+            target.set_flip(self);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Reflect)]
 pub struct AnimationFrames {
     start: AnimationIndex,
     end: AnimationIndex,
     time: AnimationDuration,
     infinite: bool,
+    end_behavior: EndBehavior,
+    mirror: Option<Mirror>,
+    anchor: Option<Vec2>,
+    #[reflect(ignore)]
+    frame_anchors: PerFrame<Vec2>,
+    #[reflect(ignore)]
+    frame_boxes: PerFrame<Vec<FrameBox>>,
+    #[reflect(ignore)]
+    frame_sockets: PerFrame<Vec<FrameSocket>>,
+    #[reflect(ignore)]
+    frame_metadata: PerFrame<HashMap<String, FrameValue>>,
+    #[reflect(ignore)]
+    tags: Vec<FrameTag>,
+    /// Asset paths of the frames of a state made of separate images.
+    #[reflect(ignore)]
+    images: Vec<String>,
 }
 
 impl std::fmt::Display for AnimationFrames {
@@ -19,7 +58,8 @@ impl std::fmt::Display for AnimationFrames {
         writeln!(f, "Start index: {}", self.start)?;
         writeln!(f, "End index: {}", self.end)?;
         writeln!(f, "Animation duration secs: {}", self.time.as_secs_f32())?;
-        writeln!(f, "Is infinite: {}", self.is_infinite())
+        writeln!(f, "Is infinite: {}", self.is_infinite())?;
+        writeln!(f, "End behavior: {:?}", self.end_behavior())
     }
 }
 impl AnimationFrames {
@@ -59,9 +99,43 @@ impl AnimationFrames {
             end,
             time,
             infinite,
+            end_behavior: EndBehavior::default(),
+            mirror: None,
+            anchor: None,
+            frame_anchors: PerFrame::default(),
+            frame_boxes: PerFrame::default(),
+            frame_sockets: PerFrame::default(),
+            frame_metadata: PerFrame::default(),
+            tags: Vec::new(),
+            images: Vec::new(),
         })
     }
 
+    /// State whose frames are separate images instead of cells of the texture atlas.
+    /// Its indexes go from zero to the last image.
+    /// # Errors
+    /// Returns an error if `images` is empty.
+    pub fn from_images(
+        images: Vec<String>,
+        time: AnimationDuration,
+        infinite: bool,
+    ) -> AnimationFrameResult {
+        if images.is_empty() {
+            return Err(AnimationFrameError::NoImages);
+        }
+        let mut frames = Self::new(
+            0,
+            None,
+            None,
+            Some(images.len()),
+            time,
+            images.len(),
+            infinite,
+        )?;
+        frames.images = images;
+        Ok(frames)
+    }
+
     pub fn from_row(
         row: AnimationIndex,
         time: AnimationDuration,
@@ -79,6 +153,10 @@ impl AnimationFrames {
         self.start
     }
 
+    pub fn frame_count(&self) -> usize {
+        self.end - self.start + 1
+    }
+
     pub fn frame_gap(&self) -> usize {
         self.end - self.start
     }
@@ -87,10 +165,15 @@ impl AnimationFrames {
         self.infinite
     }
 
+    /// A state with a single frame shows it for the whole [`Self::time`].
     pub fn time_per_frame(&self) -> AnimationDuration {
-        let time_secs = self.time().as_secs_f32();
-        let secs = time_secs / self.frame_gap() as f32;
-        AnimationDuration::from_secs_f32(secs)
+        self.time / self.frame_gap().max(1) as u32
+    }
+
+    /// Time until the last frame is finished.
+    /// Is one frame longer than [`Self::time`] which ends once the last frame is reached.
+    pub fn total_time(&self) -> AnimationDuration {
+        self.time_per_frame() * self.frame_count() as u32
     }
     pub fn precent(&self, index: usize) -> PercentScaleFactor {
         if index < self.start {
@@ -117,4 +200,299 @@ impl AnimationFrames {
     pub fn end(&self) -> usize {
         self.end
     }
+
+    pub fn set_time(&mut self, time: AnimationDuration) {
+        self.time = time;
+    }
+
+    pub fn set_infinite(&mut self, infinite: bool) {
+        self.infinite = infinite;
+    }
+
+    /// Is only used if the state is not infinite.
+    pub fn end_behavior(&self) -> EndBehavior {
+        self.end_behavior
+    }
+
+    pub fn set_end_behavior(&mut self, end_behavior: EndBehavior) {
+        self.end_behavior = end_behavior;
+    }
+
+    pub fn with_end_behavior(mut self, end_behavior: EndBehavior) -> Self {
+        self.end_behavior = end_behavior;
+        self
+    }
+
+    /// Is set for a state which shows the frames of another state flipped.
+    pub fn mirror(&self) -> Option<Mirror> {
+        self.mirror
+    }
+
+    pub fn set_mirror(&mut self, mirror: Option<Mirror>) {
+        self.mirror = mirror;
+    }
+
+    /// Custom anchor of the sprite for all frames without an own anchor.
+    /// Top left is `(-0.5, 0.5)` like in [`Anchor::Custom`](bevy::sprite::Anchor::Custom).
+    pub fn anchor(&self) -> Option<Vec2> {
+        self.anchor
+    }
+
+    pub fn set_anchor(&mut self, anchor: Option<Vec2>) {
+        self.anchor = anchor;
+    }
+
+    pub fn with_anchor(mut self, anchor: Vec2) -> Self {
+        self.anchor = Some(anchor);
+        self
+    }
+
+    /// Sets the anchor of the frame `offset` counted from the first frame.
+    /// `None` lets the frame use the anchor of the state.
+    pub fn set_frame_anchor(&mut self, offset: usize, anchor: Option<Vec2>) {
+        self.frame_anchors.set(offset, anchor);
+    }
+
+    /// Anchors of single frames by their offset from the first frame.
+    pub fn frame_anchors(&self) -> impl Iterator<Item = (usize, Vec2)> + '_ {
+        self.frame_anchors
+            .iter()
+            .map(|(offset, anchor)| (offset, *anchor))
+    }
+
+    pub fn has_anchors(&self) -> bool {
+        self.anchor.is_some() || !self.frame_anchors.is_empty()
+    }
+
+    /// Anchor of the frame at `index`, mirrored like the frame itself.
+    pub fn anchor_at(&self, index: AnimationIndex) -> Option<Vec2> {
+        let offset = index.checked_sub(self.start)?;
+        let anchor = self.frame_anchors.get(offset).copied().or(self.anchor)?;
+        let mirror = self.mirror.unwrap_or_default();
+        Some(Vec2::new(
+            if mirror.x { -anchor.x } else { anchor.x },
+            if mirror.y { -anchor.y } else { anchor.y },
+        ))
+    }
+
+    /// Sets the boxes of the frame `offset` counted from the first frame.
+    /// Replaces all boxes the frame had before.
+    pub fn set_frame_boxes(&mut self, offset: usize, boxes: Vec<FrameBox>) {
+        self.frame_boxes
+            .set(offset, Some(boxes).filter(|boxes| !boxes.is_empty()));
+    }
+
+    pub fn with_frame_boxes(mut self, offset: usize, boxes: Vec<FrameBox>) -> Self {
+        self.set_frame_boxes(offset, boxes);
+        self
+    }
+
+    pub fn has_boxes(&self) -> bool {
+        !self.frame_boxes.is_empty()
+    }
+
+    /// Boxes of the frame at `index` as they are defined, without any flip applied.
+    pub fn boxes_at(&self, index: AnimationIndex) -> &[FrameBox] {
+        index
+            .checked_sub(self.start)
+            .and_then(|offset| self.frame_boxes.get(offset))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Sets the sockets of the frame `offset` counted from the first frame.
+    /// Replaces all sockets the frame had before.
+    pub fn set_frame_sockets(&mut self, offset: usize, sockets: Vec<FrameSocket>) {
+        self.frame_sockets
+            .set(offset, Some(sockets).filter(|sockets| !sockets.is_empty()));
+    }
+
+    pub fn with_frame_sockets(mut self, offset: usize, sockets: Vec<FrameSocket>) -> Self {
+        self.set_frame_sockets(offset, sockets);
+        self
+    }
+
+    /// Sockets of the frame at `index` as they are defined, without any flip applied.
+    pub fn sockets_at(&self, index: AnimationIndex) -> &[FrameSocket] {
+        index
+            .checked_sub(self.start)
+            .and_then(|offset| self.frame_sockets.get(offset))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn socket_at(&self, index: AnimationIndex, name: &str) -> Option<FrameSocket> {
+        self.sockets_at(index)
+            .iter()
+            .find(|socket| socket.name == name)
+            .copied()
+    }
+
+    /// Sets `key` to `value` for the frame `offset` counted from the first frame.
+    pub fn insert_frame_value(
+        &mut self,
+        offset: usize,
+        key: impl Into<String>,
+        value: impl Into<FrameValue>,
+    ) {
+        self.frame_metadata
+            .get_or_insert_default(offset)
+            .insert(key.into(), value.into());
+    }
+
+    /// Metadata of the frame at `index`.
+    pub fn metadata_at(&self, index: AnimationIndex) -> Option<&HashMap<String, FrameValue>> {
+        index
+            .checked_sub(self.start)
+            .and_then(|offset| self.frame_metadata.get(offset))
+    }
+
+    /// Adds a named range of frames. A tag can cover frames which are already in another tag.
+    /// # Errors
+    /// Returns an error if `from` is greater than `to` or `to` is not a frame of this state.
+    pub fn add_tag(&mut self, tag: FrameTag) -> Result<(), AnimationFrameError> {
+        if tag.from > tag.to || tag.to >= self.frame_count() {
+            return Err(AnimationFrameError::TagOutOfFrames {
+                name: tag.name.to_string(),
+                from: tag.from,
+                to: tag.to,
+                frame_count: self.frame_count(),
+            });
+        }
+        self.tags.retain(|other| other.name != tag.name);
+        self.tags.push(tag);
+        Ok(())
+    }
+
+    /// # Errors
+    /// See [`Self::add_tag`].
+    pub fn with_tag(mut self, tag: FrameTag) -> AnimationFrameResult {
+        self.add_tag(tag)?;
+        Ok(self)
+    }
+
+    pub fn tags(&self) -> &[FrameTag] {
+        &self.tags
+    }
+
+    pub fn has_tags(&self) -> bool {
+        !self.tags.is_empty()
+    }
+
+    pub fn tag(&self, name: &str) -> Option<FrameTag> {
+        self.tags.iter().find(|tag| tag.name == name).copied()
+    }
+
+    /// Names of all tags containing the frame at `index`.
+    pub fn tags_at(&self, index: AnimationIndex) -> impl Iterator<Item = &'static str> + '_ {
+        let offset = index.checked_sub(self.start);
+        self.tags
+            .iter()
+            .filter(move |tag| offset.is_some_and(|offset| tag.contains_offset(offset)))
+            .map(|tag| tag.name)
+    }
+
+    pub fn is_in_tag(&self, name: &str, index: AnimationIndex) -> bool {
+        self.tags_at(index).any(|tag| tag == name)
+    }
+
+    /// First and last index of the tag `name` inside the sprite sheet.
+    pub fn tag_indexes(&self, name: &str) -> Option<(AnimationIndex, AnimationIndex)> {
+        self.tag(name)
+            .map(|tag| (self.start + tag.from, self.start + tag.to))
+    }
+
+    /// Progress of the first and the last frame of the tag `name` like [`Self::precent`].
+    pub fn tag_precents(&self, name: &str) -> Option<(PercentScaleFactor, PercentScaleFactor)> {
+        self.tag_indexes(name)
+            .map(|(from, to)| (self.precent(from), self.precent(to)))
+    }
+
+    /// Asset paths of the frames. Is empty for a state of the texture atlas.
+    pub fn images(&self) -> &[String] {
+        &self.images
+    }
+
+    pub fn is_image_sequence(&self) -> bool {
+        !self.images.is_empty()
+    }
+
+    pub fn image_at(&self, index: AnimationIndex) -> Option<&str> {
+        index
+            .checked_sub(self.start)
+            .and_then(|offset| self.images.get(offset))
+            .map(String::as_str)
+    }
+
+    /// Images of up to `count` frames which are shown after the frame at `index`.
+    /// Starts again at the first frame for an infinite state.
+    pub fn images_after(
+        &self,
+        index: AnimationIndex,
+        count: usize,
+    ) -> impl Iterator<Item = &str> + '_ {
+        let offset = index.saturating_sub(self.start);
+        let count = if self.infinite {
+            count.min(self.images.len().saturating_sub(1))
+        } else {
+            count
+        };
+        (1..=count)
+            .map(move |step| offset + step)
+            .filter(|next| self.infinite || *next < self.images.len())
+            .map(|next| self.images[next % self.images.len()].as_str())
+    }
+
+    /// Same frames which are shown flipped by `mirror` on top of their own mirror.
+    pub fn mirrored(&self, mirror: Mirror) -> Self {
+        let own = self.mirror.unwrap_or_default();
+        Self {
+            mirror: Some(Mirror {
+                x: own.x ^ mirror.x,
+                y: own.y ^ mirror.y,
+            }),
+            ..self.clone()
+        }
+    }
+
+    /// # Errors
+    /// Returns an error if `start` is greater than `end`.
+    pub fn set_indexes(
+        &mut self,
+        start: AnimationIndex,
+        end: AnimationIndex,
+    ) -> Result<(), AnimationFrameError> {
+        if start > end {
+            return Err(AnimationFrameError::InvalidIndexes { start, end });
+        }
+        self.start = start;
+        self.end = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn preloads_following_images() {
+        let images: Vec<String> = (0..4).map(|index| format!("intro/{}.png", index)).collect();
+        let mut frames =
+            AnimationFrames::from_images(images, AnimationDuration::from_secs(1), true).unwrap();
+        assert_eq!((0, 3), (frames.start(), frames.end()));
+        assert_eq!(Some("intro/2.png"), frames.image_at(2));
+        assert_eq!(
+            vec!["intro/3.png", "intro/0.png"],
+            frames.images_after(2, 2).collect::<Vec<_>>()
+        );
+        assert_eq!(3, frames.images_after(0, 10).count());
+
+        frames.set_infinite(false);
+        assert_eq!(
+            vec!["intro/3.png"],
+            frames.images_after(2, 2).collect::<Vec<_>>()
+        );
+        assert!(AnimationFrames::from_images(Vec::new(), AnimationDuration::ZERO, true).is_err());
+    }
 }
@@ -0,0 +1,256 @@
+use bevy::prelude::*;
+
+#[cfg(feature = "bevy_inspect")]
+use bevy_inspector_egui::prelude::*;
+
+use crate::{
+    animation_comp::AnimationComp,
+    collection_look_up::CollectionId,
+    types::{AnimationIndex, AnimationReference},
+};
+
+/// What happened to the animation of an entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LifecycleKind {
+    /// A state started playing, either as the first state of an entity or after a switch.
+    Started,
+    /// The sprite moved from the frame `from` to the frame of the event.
+    FrameChanged { from: AnimationIndex },
+    /// The last frame of a repeating state ended and the state starts again.
+    LoopCompleted,
+    /// The last frame of a state without repeat ended.
+    Finished,
+    /// The state was left for `next` before it finished.
+    Interrupted { next: AnimationReference },
+    /// The state was started again via [`AnimationComp::reset_current_state`].
+    Reset,
+    /// The collection of the entity was changed at runtime.
+    /// If it was removed, the [`AnimationComp`] of the entity is removed as well.
+    CollectionChanged,
+}
+
+/// Is sent for entities with [`ListenLifecycle`] if its filter contains the kind of the event.
+#[derive(Event, Debug, Clone)]
+pub struct AnimationLifecycle {
+    pub who: Entity,
+    pub kind: LifecycleKind,
+    pub collection: CollectionId,
+    pub state: AnimationReference,
+    /// Completed loops of `state` including the one of this event.
+    pub loop_count: u32,
+    /// Index of the sprite when the event was sent.
+    pub frame: AnimationIndex,
+}
+
+impl AnimationLifecycle {
+    pub(crate) fn new(
+        who: Entity,
+        kind: LifecycleKind,
+        animator: &AnimationComp,
+        frame: AnimationIndex,
+    ) -> Self {
+        Self {
+            who,
+            kind,
+            collection: animator.collection_id(),
+            state: animator.current_state.clone(),
+            loop_count: animator.loop_count,
+            frame,
+        }
+    }
+}
+
+/// Set of [`LifecycleKind`]s. Sets are combined via `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct LifecycleFilter(u8);
+
+impl LifecycleFilter {
+    pub const NONE: Self = Self(0);
+    pub const STARTED: Self = Self(1);
+    pub const FRAME_CHANGED: Self = Self(1 << 1);
+    pub const LOOP_COMPLETED: Self = Self(1 << 2);
+    pub const FINISHED: Self = Self(1 << 3);
+    pub const INTERRUPTED: Self = Self(1 << 4);
+    pub const RESET: Self = Self(1 << 5);
+    pub const COLLECTION_CHANGED: Self = Self(1 << 6);
+    pub const ALL: Self = Self((1 << 7) - 1);
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn allows(self, kind: &LifecycleKind) -> bool {
+        self.contains(Self::of(kind))
+    }
+
+    fn of(kind: &LifecycleKind) -> Self {
+        match kind {
+            LifecycleKind::Started => Self::STARTED,
+            LifecycleKind::FrameChanged { .. } => Self::FRAME_CHANGED,
+            LifecycleKind::LoopCompleted => Self::LOOP_COMPLETED,
+            LifecycleKind::Finished => Self::FINISHED,
+            LifecycleKind::Interrupted { .. } => Self::INTERRUPTED,
+            LifecycleKind::Reset => Self::RESET,
+            LifecycleKind::CollectionChanged => Self::COLLECTION_CHANGED,
+        }
+    }
+}
+
+impl Default for LifecycleFilter {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl std::ops::BitOr for LifecycleFilter {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for LifecycleFilter {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Entity sends [`AnimationLifecycle`] events for all kinds inside the filter.
+/// Listens to all kinds by default.
+#[derive(Debug, Default, Component, Reflect)]
+#[cfg_attr(
+    feature = "bevy_inspect",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+pub struct ListenLifecycle(pub LifecycleFilter);
+
+impl ListenLifecycle {
+    /// Pushes an event of `kind` if `listen` is present and allows it.
+    pub(crate) fn report(
+        listen: Option<&Self>,
+        events: &mut Vec<AnimationLifecycle>,
+        who: Entity,
+        kind: LifecycleKind,
+        animator: &AnimationComp,
+        frame: AnimationIndex,
+    ) {
+        if listen.is_some_and(|listen| listen.0.allows(&kind)) {
+            events.push(AnimationLifecycle::new(who, kind, animator, frame));
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        prelude::{AllAnimationResource, AnimationCollectionBuilder},
+        test_app::{self, KEY},
+    };
+
+    /// `Loop` repeats on the first row and `Once` plays the second row once, both in one second.
+    fn app_with(filter: LifecycleFilter) -> (App, Entity) {
+        let mut app = test_app::app();
+        let time = test_app::ROW_TIME;
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_row_ani("Loop", 0, time, true)
+            .add_row_ani("Once", 1, time, false)
+            .build("Loop");
+        test_app::add_collection(&mut app, collection);
+        let sprite = test_app::spawn_sprite(&mut app);
+        app.world.entity_mut(sprite).insert(ListenLifecycle(filter));
+        (app, sprite)
+    }
+
+    fn kinds_after(app: &mut App, delta: Duration) -> Vec<LifecycleKind> {
+        test_app::tick(app, delta);
+        test_app::drain_events::<AnimationLifecycle>(app)
+            .into_iter()
+            .map(|event| event.kind)
+            .collect()
+    }
+
+    fn animator(app: &mut App, sprite: Entity) -> Mut<'_, AnimationComp> {
+        app.world.get_mut::<AnimationComp>(sprite).unwrap()
+    }
+
+    #[test]
+    fn reports_every_kind() {
+        let (mut app, sprite) = app_with(LifecycleFilter::ALL);
+        assert_eq!(
+            vec![LifecycleKind::Started],
+            kinds_after(&mut app, Duration::ZERO)
+        );
+        assert_eq!(
+            vec![LifecycleKind::FrameChanged { from: 0 }],
+            kinds_after(&mut app, Duration::from_millis(300))
+        );
+        let kinds = kinds_after(&mut app, Duration::from_millis(800));
+        assert!(kinds.contains(&LifecycleKind::LoopCompleted));
+        assert!(kinds.contains(&LifecycleKind::FrameChanged { from: 1 }));
+
+        animator(&mut app, sprite).set_state("Once");
+        let kinds = kinds_after(&mut app, Duration::ZERO);
+        assert!(kinds.contains(&LifecycleKind::Interrupted {
+            next: "Once".into()
+        }));
+        assert!(kinds.contains(&LifecycleKind::Started));
+        let kinds = kinds_after(&mut app, Duration::from_secs(2));
+        assert!(kinds.contains(&LifecycleKind::Finished));
+
+        animator(&mut app, sprite).reset_current_state();
+        assert_eq!(
+            vec![LifecycleKind::Reset],
+            kinds_after(&mut app, Duration::ZERO)
+        );
+        kinds_after(&mut app, Duration::from_secs(2));
+
+        // Leaving a finished state is no interruption.
+        animator(&mut app, sprite).set_state("Loop");
+        assert!(!animator(&mut app, sprite).has_reached_end_without_repeat);
+        assert_eq!(
+            vec![LifecycleKind::Started],
+            kinds_after(&mut app, Duration::ZERO)
+        );
+
+        app.world
+            .resource_mut::<AllAnimationResource>()
+            .remove_animations(KEY);
+        animator(&mut app, sprite).reset_current_state();
+        assert_eq!(
+            vec![LifecycleKind::CollectionChanged],
+            kinds_after(&mut app, Duration::ZERO)
+        );
+        assert!(app.world.get::<AnimationComp>(sprite).is_none());
+    }
+
+    #[test]
+    fn reports_only_kinds_inside_filter() {
+        let filter = LifecycleFilter::STARTED | LifecycleFilter::FINISHED;
+        assert!(filter.contains(LifecycleFilter::FINISHED));
+        assert!(!filter.allows(&LifecycleKind::LoopCompleted));
+        assert!(LifecycleFilter::ALL.contains(filter));
+        assert!(!LifecycleFilter::NONE.allows(&LifecycleKind::Started));
+
+        let (mut app, sprite) = app_with(filter);
+        assert_eq!(
+            vec![LifecycleKind::Started],
+            kinds_after(&mut app, Duration::ZERO)
+        );
+        assert!(kinds_after(&mut app, Duration::from_millis(1_100)).is_empty());
+
+        animator(&mut app, sprite).set_state("Once");
+        assert_eq!(
+            vec![LifecycleKind::Started],
+            kinds_after(&mut app, Duration::ZERO)
+        );
+        assert_eq!(
+            vec![LifecycleKind::Finished],
+            kinds_after(&mut app, Duration::from_secs(2))
+        );
+    }
+}
@@ -1,28 +1,57 @@
 use std::borrow::Cow;
 
 use crate::{
+    animation_altlas::TextureAtlasCache,
     animation_collection::AnimationCollection,
+    animation_collection_changed::StateChange,
     animation_comp::AnimationComp,
-    animation_error::NotFoundError,
+    animation_error::{AnimationError, NotFoundError},
+    collection_look_up::CollectionId,
+    image_animation_bundle::ImageAnimationBundle,
+    namespace,
+    repository_inspection::{CollectionInfo, StateInfo},
     sprite_animation_bundle::SpriteAnimationBundle,
+    sprite_rect_animation_bundle::SpriteRectAnimationBundle,
     text_like::TextLike,
-    types::{AnimationRepository, KeyLookUpResult},
+    types::{AnimationIndex, AnimationRepository, AnimationResult, KeyLookUpResult},
+    ui_animation_bundle::UiAnimationBundle,
     utils, PosScaleFactor,
 };
 
 use bevy::prelude::*;
 
 #[cfg(feature = "assets")]
-use crate::{animation_error::AnimationError, save_load::AnimationAssets, types::AnimationResult};
+use asset_sources::{AssetSource, SourceId};
 #[cfg(feature = "assets")]
 use bevy::utils::HashMap;
 
+#[cfg(feature = "assets")]
+mod asset_sources;
+
 #[derive(Resource, Default, Debug)]
 pub struct AllAnimationResource {
     animation_seqs: AnimationRepository,
     #[cfg(feature = "assets")]
-    handle_to_key: HashMap<AssetId<AnimationAssets>, &'static str>,
+    asset_sources: HashMap<SourceId, AssetSource>,
     global_animation_duration: PosScaleFactor,
+    atlas_cache: TextureAtlasCache,
+    duplicate_key_policy: DuplicateKeyPolicy,
+    /// Removed collections with all their states as removed until they are propagated.
+    removed_collections: Vec<(CollectionId, Vec<StateChange>)>,
+}
+
+/// What happens if a collection is added under a key which is already taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateKeyPolicy {
+    /// Keeps the existing collection untouched and returns an error.
+    #[default]
+    Reject,
+    /// Replaces the existing collection. Entities playing it are re-synced.
+    Replace,
+    /// Adds the states of the new collection to the existing one.
+    /// States with the same name are replaced.
+    /// Both collections must use the same texture atlas.
+    Merge,
 }
 
 impl AllAnimationResource {
@@ -40,23 +69,206 @@ impl AllAnimationResource {
         self.animation_seqs.get(key).unwrap()
     }
 
+    /// Adds `collection` under `key` or logs an error if the [`DuplicateKeyPolicy`] does not allow it.
+    /// See [`Self::try_add_animations`] to handle the error instead.
     pub fn add_animations<'a>(
         &mut self,
         key: impl Into<TextLike<'a>>,
         collection: AnimationCollection,
     ) -> &mut Self {
-        self.animation_seqs
-            .insert(key.into().into_registered_name(), collection);
+        let result = self.try_add_animations(key, collection).map(|_| ());
+        utils::log_if_error(result, "Adding animations failed.");
         self
     }
 
+    /// # Errors
+    /// Returns an error if the key is already taken and the [`DuplicateKeyPolicy`] does not allow it.
+    pub fn try_add_animations<'a>(
+        &mut self,
+        key: impl Into<TextLike<'a>>,
+        collection: AnimationCollection,
+    ) -> AnimationResult<&mut Self> {
+        self.insert_collection(key.into().into_registered_name(), collection)?;
+        Ok(self)
+    }
+
+    pub fn duplicate_key_policy(&self) -> DuplicateKeyPolicy {
+        self.duplicate_key_policy
+    }
+
+    /// Decides what happens if a collection is added under an already taken key.
+    pub fn set_duplicate_key_policy(&mut self, policy: DuplicateKeyPolicy) {
+        self.duplicate_key_policy = policy;
+    }
+
+    /// Removes a collection. Its texture atlas is dropped
+    /// once no other collection or entity uses it anymore.
+    /// Entities playing it stop animating. An [`AnimationCollectionChanged`](crate::AnimationCollectionChanged)
+    /// with all its states as removed is sent in the next update.
+    pub fn remove_animations(&mut self, key: &str) -> Option<AnimationCollection> {
+        #[cfg(feature = "assets")]
+        self.asset_sources.retain(|_, source| source.key != key);
+        let (key, collection) = self.animation_seqs.remove_entry(key)?;
+        let changes = collection
+            .frames()
+            .keys()
+            .map(|&state| StateChange::Removed(state))
+            .collect();
+        self.removed_collections
+            .push((CollectionId::Key(key), changes));
+        Some(collection)
+    }
+
+    /// Texture atlases shared between collections using the same image and grid.
+    /// Can be passed to [`AnimationAltlasMeta::build_shared`](crate::prelude::AnimationAltlasMeta::build_shared)
+    /// to share atlases with collections created outside of this resource.
+    pub fn atlas_cache_mut(&mut self) -> &mut TextureAtlasCache {
+        &mut self.atlas_cache
+    }
+
+    pub fn collection_keys(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.animation_seqs.keys().copied()
+    }
+
+    /// Keys of all collections inside `namespace`, for example `enemies` for `enemies/goblin`.
+    pub fn collection_keys_in<'a>(
+        &'a self,
+        namespace: &'a str,
+    ) -> impl Iterator<Item = &'static str> + 'a {
+        self.collections_in(namespace).map(|(key, _)| key)
+    }
+
+    pub fn collections_in<'a>(
+        &'a self,
+        namespace: &'a str,
+    ) -> impl Iterator<Item = (&'static str, &'a AnimationCollection)> + 'a {
+        self.animation_seqs
+            .iter()
+            .filter(move |(key, _)| namespace::is_in_namespace(key, namespace))
+            .map(|(&key, collection)| (key, collection))
+    }
+
+    /// Removes all collections inside `namespace`.
+    pub fn remove_namespace(&mut self, namespace: &str) -> Vec<AnimationCollection> {
+        let to_remove: Vec<&'static str> = self.collection_keys_in(namespace).collect();
+        to_remove
+            .into_iter()
+            .filter_map(|key| self.remove_animations(key))
+            .collect()
+    }
+
+    /// Scales the animation speed of all collections inside `namespace`.
+    /// Applies on top of the [`AnimationTimeScale`](crate::AnimationTimeScale) of each entity.
+    pub fn set_time_scale_in(&mut self, namespace: &str, time_scale: PosScaleFactor) {
+        self.animation_seqs
+            .iter_mut()
+            .filter(|(key, _)| namespace::is_in_namespace(key, namespace))
+            .for_each(|(_, collection)| collection.set_time_scale(time_scale));
+    }
+
+    /// # Errors
+    /// Returns an error if there is no collection under `key`.
+    pub fn collection_info(&self, key: &str) -> KeyLookUpResult<CollectionInfo> {
+        let (&key, collection) = self
+            .animation_seqs
+            .get_key_value(key)
+            .ok_or_else(|| NotFoundError::AnimationSequence(key.into()))?;
+        #[allow(unused_mut)]
+        let mut info = CollectionInfo::new(Some(key), collection);
+        #[cfg(feature = "assets")]
+        {
+            info.sources = self.source_assets(key);
+        }
+        Ok(info)
+    }
+
+    /// Snapshots of all collections sorted by key.
+    pub fn collection_infos(&self) -> Vec<CollectionInfo> {
+        let mut keys: Vec<&'static str> = self.collection_keys().collect();
+        keys.sort_unstable();
+        keys.into_iter()
+            .filter_map(|key| self.collection_info(key).ok())
+            .collect()
+    }
+
+    /// # Errors
+    /// Returns an error if there is no collection under `key` or it has no state named `state`.
+    pub fn state_info(&self, key: &str, state: &str) -> KeyLookUpResult<StateInfo> {
+        self.animation_under(key)?.state_info(state)
+    }
+
     pub fn animation_under(&self, key: &str) -> Result<&AnimationCollection, NotFoundError> {
         self.animation_seqs
             .get(key)
             .ok_or_else(|| NotFoundError::AnimationSequence(key.into()))
     }
 
+    /// Changes to the states of the returned collection are propagated
+    /// to the entities playing them in the next update.
+    pub fn animation_under_mut(
+        &mut self,
+        key: &str,
+    ) -> Result<&mut AnimationCollection, NotFoundError> {
+        self.animation_seqs
+            .get_mut(key)
+            .ok_or_else(|| NotFoundError::AnimationSequence(key.into()))
+    }
+
+    pub(crate) fn has_pending_changes(&self) -> bool {
+        !self.removed_collections.is_empty()
+            || self
+                .animation_seqs
+                .values()
+                .any(AnimationCollection::has_pending_changes)
+    }
+
+    pub(crate) fn take_changes(&mut self) -> Vec<(CollectionId, Vec<StateChange>)> {
+        let mut changes = std::mem::take(&mut self.removed_collections);
+        changes.extend(
+            self.animation_seqs
+                .iter_mut()
+                .filter(|(_, collection)| collection.has_pending_changes())
+                .map(|(&key, collection)| (CollectionId::Key(key), collection.take_changes())),
+        );
+        changes
+    }
+
     pub fn create_sprite_comp(&self, key: &str) -> KeyLookUpResult<SpriteAnimationBundle> {
+        let (frames, animations, start_index) = self.start_of(key)?;
+        Ok(SpriteAnimationBundle::new(frames, animations, start_index))
+    }
+
+    /// Same as [`Self::create_sprite_comp`] for a node of `bevy_ui`.
+    pub fn create_ui_comp(&self, key: &str) -> KeyLookUpResult<UiAnimationBundle> {
+        let (frames, animations, start_index) = self.start_of(key)?;
+        Ok(UiAnimationBundle::new(frames, animations, start_index))
+    }
+
+    /// Same as [`Self::create_sprite_comp`] for a plain sprite showing cells of `image` via its rect.
+    pub fn create_sprite_rect_comp(
+        &self,
+        key: &str,
+        image: Handle<Image>,
+    ) -> KeyLookUpResult<SpriteRectAnimationBundle> {
+        let (frames, animations, start_index) = self.start_of(key)?;
+        Ok(SpriteRectAnimationBundle::new(
+            frames,
+            animations,
+            start_index,
+            image,
+        ))
+    }
+
+    /// Same as [`Self::create_sprite_comp`] for states made of separate images.
+    pub fn create_image_comp(&self, key: &str) -> KeyLookUpResult<ImageAnimationBundle> {
+        let (frames, animations, start_index) = self.start_of(key)?;
+        Ok(ImageAnimationBundle::new(frames, animations, start_index))
+    }
+
+    fn start_of(
+        &self,
+        key: &str,
+    ) -> KeyLookUpResult<(AnimationComp, &AnimationCollection, AnimationIndex)> {
         type StrRef = Cow<'static, str>;
 
         let (frames_key, animations) = self
@@ -66,93 +278,47 @@ impl AllAnimationResource {
         let (frame_key, start_state): (StrRef, StrRef) =
             (Cow::Borrowed(*frames_key), animations.start_state().into());
         let frames = AnimationComp::new(frame_key, start_state, self)?;
-        let sprite_sheet = SpriteSheetBundle {
-            texture_atlas: animations.atlas(),
-            sprite: TextureAtlasSprite::new(frames.start_index(self)?),
-            ..default()
-        };
-        Ok(SpriteAnimationBundle {
-            sprite_sheet,
-            time_scale: Default::default(),
-            frames,
-        })
+        let start_index = frames.start_index(self)?;
+        Ok((frames, animations, start_index))
     }
 
-    #[cfg(feature = "assets")]
-    pub fn add_from_asset<'a>(
+    /// Every way of adding a collection must go through here
+    /// so the [`DuplicateKeyPolicy`] is applied.
+    pub(crate) fn insert_collection(
         &mut self,
-        animations: Handle<AnimationAssets>,
-        image: Handle<Image>,
-        asset_atlases: &mut Assets<TextureAtlas>,
-        asset_animation: &Assets<AnimationAssets>,
-        key: Option<impl Into<TextLike<'a>>>,
-    ) -> AnimationResult<&mut Self> {
-        let Some(animations_loaded) = asset_animation.get(&animations) else {
-            return Err(AnimationError::AnimationNotLoadedYet);
-        };
-        let animations_loaded = animations_loaded.clone();
-
-        let static_key = {
-            let key: TextLike = match (animations_loaded.name(), key) {
-                (_, Some(from_func_call)) => from_func_call.into(),
-                (Some(from_asset), _) => TextLike::Owned(from_asset.to_string()),
-                _ => {
-                    return Err(AnimationError::NoSeqeunceKeyProvided);
-                }
-            };
-            key.into_registered_name()
+        key: &'static str,
+        collection: AnimationCollection,
+    ) -> AnimationResult {
+        self.check_insert(key, &collection)?;
+        let Some(existing) = self.animation_seqs.get_mut(key) else {
+            self.animation_seqs.insert(key, collection);
+            info!("New animations are added under new key ({})", key);
+            return Ok(());
         };
-
-        self.inner_add_from_asset(static_key, animations_loaded.clone(), image, asset_atlases)?;
-        self.handle_to_key.insert(animations.id(), static_key);
-
-        Ok(self)
+        if self.duplicate_key_policy == DuplicateKeyPolicy::Merge {
+            existing.merge(collection);
+            info!("Animations are merged into key ({})", key);
+        } else {
+            existing.replace_with(collection);
+            info!("Animations under key ({}) are replaced", key);
+        }
+        Ok(())
     }
 
-    #[cfg(feature = "assets")]
-    pub fn replace_from_assets(
-        &mut self,
-        animations_id: &AssetId<AnimationAssets>,
-        assets_animations: &Assets<AnimationAssets>,
-    ) -> AnimationResult<&mut Self> {
-        match assets_animations.get(*animations_id) {
-            Some(animations) => {
-                let name = *self.handle_to_key.get(animations_id).unwrap();
-
-                let to_change = self
-                    .animation_seqs
-                    .get_mut(name)
-                    .ok_or_else(|| NotFoundError::AnimationSequence(name.to_string()))?;
-                let new_seq = animations.to_ani_seq(self.global_animation_duration)?;
-                to_change.set_frames(new_seq);
-                Ok(self)
+    /// Returns the error [`Self::insert_collection`] would return without changing anything.
+    fn check_insert(&self, key: &'static str, collection: &AnimationCollection) -> AnimationResult {
+        match (self.animation_seqs.get(key), self.duplicate_key_policy) {
+            (Some(_), DuplicateKeyPolicy::Reject) => Err(
+                AnimationError::DuplicateKeySequenceProvided(key.to_string()),
+            ),
+            (Some(existing), DuplicateKeyPolicy::Merge)
+                if existing.atlas() != collection.atlas() =>
+            {
+                Err(AnimationError::MergeWithDifferentAtlas(key.to_string()))
             }
-            None => Ok(self),
+            _ => Ok(()),
         }
     }
-
-    #[cfg(feature = "assets")]
-    fn inner_add_from_asset(
-        &mut self,
-        key: &'static str,
-        animations: AnimationAssets,
-        image: Handle<Image>,
-        asset_atlases: &mut Assets<TextureAtlas>,
-    ) -> AnimationResult {
-        let collection = animations.to_animaton_collection(
-            image,
-            asset_atlases,
-            self.global_animation_duration,
-        )?;
-        if self.animation_seqs.insert(key, collection).is_some() {
-            return Err(AnimationError::DuplicateKeySequenceProvided(
-                key.to_string(),
-            ));
-        } else {
-            info!("New animations are added under new key ({})", key)
-        };
-        Ok(())
-    }
 }
 
 impl std::fmt::Display for AllAnimationResource {
@@ -175,11 +341,192 @@ impl std::fmt::Display for AllAnimationResource {
         #[cfg(feature = "assets")]
         {
             writeln!(f, "The following sequences are backed behind a reference\n")?;
-            for key in self.handle_to_key.values() {
-                write!(f, "- {}", key)?;
+            for source in self.asset_sources.values() {
+                write!(f, "- {}", source.key)?;
             }
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod testing {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        animation_frames::AnimationFrames,
+        prelude::{AnimationAltlasMeta, AnimationCollectionBuilder, AnimationDuration},
+        test_app, PlaybackMode,
+    };
+
+    fn collection(atlases: &mut Assets<TextureAtlas>) -> AnimationCollection {
+        let meta =
+            AnimationAltlasMeta::new(4, 4, Vec2::splat(16.)).build(Handle::default(), atlases);
+        AnimationCollectionBuilder::new(meta)
+            .add_row_ani("Idle", 0, AnimationDuration::from_secs(1), true)
+            .build("Idle")
+    }
+
+    fn resource(keys: &[&str]) -> AllAnimationResource {
+        let mut atlases = Assets::default();
+        let mut repos = AllAnimationResource::default();
+        for key in keys {
+            repos
+                .try_add_animations(key.to_string(), collection(&mut atlases))
+                .unwrap();
+        }
+        repos
+    }
+
+    #[test]
+    fn rejects_taken_key_without_touching_old_collection() {
+        let mut repos = resource(&["hero"]);
+        let mut atlases = Assets::default();
+        let mut other = collection(&mut atlases);
+        other.insert_state(
+            "Walk",
+            AnimationFrames::from_row(1, AnimationDuration::from_secs(1), 4, true).unwrap(),
+        );
+
+        let result = repos.try_add_animations("hero", other);
+        assert!(
+            matches!(result, Err(AnimationError::DuplicateKeySequenceProvided(key)) if key == "hero")
+        );
+        let states: Vec<_> = repos.state_names_under("hero").collect();
+        assert_eq!(vec!["Idle"], states);
+    }
+
+    #[test]
+    fn merges_only_with_same_atlas() {
+        let mut atlases = Assets::default();
+        let mut repos = AllAnimationResource::default();
+        repos.add_animations("hero", collection(&mut atlases));
+        repos.set_duplicate_key_policy(DuplicateKeyPolicy::Merge);
+        let other = collection(&mut atlases);
+        assert_ne!(
+            repos.animation_under("hero").unwrap().atlas(),
+            other.atlas()
+        );
+
+        let result = repos.try_add_animations("hero", other);
+        assert!(
+            matches!(result, Err(AnimationError::MergeWithDifferentAtlas(key)) if key == "hero")
+        );
+    }
+
+    #[test]
+    fn replacing_resyncs_playing_entities() {
+        let mut app = test_app::app();
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_row_ani("Idle", 0, test_app::ROW_TIME, true)
+            .build("Idle");
+        test_app::add_collection(&mut app, collection);
+        let sprite = test_app::spawn_sprite(&mut app);
+        test_app::tick(&mut app, Duration::ZERO);
+        test_app::tick(&mut app, Duration::from_millis(600));
+
+        let replacement = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_row_ani("Idle", 2, test_app::ROW_TIME, true)
+            .build("Idle");
+        let new_atlas = replacement.atlas();
+        let mut repos = app.world.resource_mut::<AllAnimationResource>();
+        repos.set_duplicate_key_policy(DuplicateKeyPolicy::Replace);
+        repos
+            .try_add_animations(test_app::KEY, replacement)
+            .unwrap();
+        test_app::tick(&mut app, Duration::ZERO);
+
+        let index = test_app::index(&app, sprite);
+        assert_eq!(2 * test_app::COLUMNS + 2, index);
+        assert_eq!(
+            new_atlas,
+            *app.world.get::<Handle<TextureAtlas>>(sprite).unwrap()
+        );
+    }
+
+    #[test]
+    fn reports_info_of_known_state() {
+        let mut atlases = Assets::default();
+        let meta =
+            AnimationAltlasMeta::new(4, 4, Vec2::splat(16.)).build(Handle::default(), &mut atlases);
+        let atlas = meta.atlas();
+        let collection = AnimationCollectionBuilder::new(meta)
+            .add_row_ani("Idle", 0, AnimationDuration::from_secs(1), true)
+            .add_row_ani("Attack", 1, AnimationDuration::from_millis(500), false)
+            .build("Idle");
+        let mut repos = AllAnimationResource::default();
+        repos.try_add_animations("hero", collection).unwrap();
+
+        let attack = repos.state_info("hero", "Attack").unwrap();
+        assert_eq!((4, 7, 4), (attack.start, attack.end, attack.frame_count));
+        assert_eq!(AnimationDuration::from_millis(500), attack.duration);
+        assert_eq!(PlaybackMode::Once, attack.playback);
+        assert_eq!(atlas, attack.atlas);
+        assert_eq!(
+            PlaybackMode::Repeat,
+            repos.state_info("hero", "Idle").unwrap().playback
+        );
+
+        let info = repos.collection_info("hero").unwrap();
+        assert_eq!((Some("hero"), "Idle"), (info.key, info.start_state));
+        let states: Vec<_> = info.states.iter().map(|state| state.name).collect();
+        assert_eq!(vec!["Attack", "Idle"], states);
+        #[cfg(feature = "assets")]
+        assert!(info.sources.is_empty());
+    }
+
+    #[test]
+    fn reports_unknown_keys_as_error() {
+        let repos = resource(&["hero"]);
+        assert!(repos.collection_info("ghost").is_err());
+        assert!(repos.state_info("ghost", "Idle").is_err());
+        assert!(repos.state_info("hero", "Jump").is_err());
+    }
+
+    #[test]
+    fn lists_info_of_every_key() {
+        let repos = resource(&["hero", "enemies/orc", "enemies/goblin"]);
+        let keys: Vec<_> = repos
+            .collection_infos()
+            .into_iter()
+            .map(|info| info.key)
+            .collect();
+        assert_eq!(
+            vec![Some("enemies/goblin"), Some("enemies/orc"), Some("hero")],
+            keys
+        );
+    }
+
+    #[test]
+    fn removes_only_collections_inside_namespace() {
+        let mut repos = resource(&[
+            "enemies/goblin",
+            "enemies/orc/elite",
+            "enemies_boss",
+            "hero",
+        ]);
+
+        let removed = repos.remove_namespace("enemies");
+        assert_eq!(2, removed.len());
+        let mut left: Vec<_> = repos.collection_keys().collect();
+        left.sort_unstable();
+        assert_eq!(vec!["enemies_boss", "hero"], left);
+    }
+
+    #[test]
+    fn scales_time_only_inside_namespace() {
+        let mut repos = resource(&["enemies/goblin", "enemies/orc/elite", "hero"]);
+        let slow = PosScaleFactor::new(0.5).unwrap();
+
+        repos.set_time_scale_in("enemies", slow);
+        for key in ["enemies/goblin", "enemies/orc/elite"] {
+            assert_eq!(slow, repos.animation_under(key).unwrap().time_scale());
+        }
+        assert_eq!(
+            PosScaleFactor::default(),
+            repos.animation_under("hero").unwrap().time_scale()
+        );
+    }
+}
@@ -0,0 +1,609 @@
+use bevy::{asset::UntypedAssetId, prelude::*};
+
+use super::{AllAnimationResource, DuplicateKeyPolicy};
+use crate::{
+    animation_collection::AnimationCollection,
+    animation_error::{AnimationError, NotFoundError},
+    namespace,
+    save_load::{AnimationAssets, AnimationCollectionsAssets},
+    text_like::TextLike,
+    types::AnimationResult,
+};
+
+/// File a collection was loaded from.
+/// For a file with several collections also the name of the collection inside it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct SourceId {
+    file: UntypedAssetId,
+    entry: Option<&'static str>,
+}
+
+/// Remembers which states of a collection came from which file.
+/// A collection can be made of several files if they are merged via [`DuplicateKeyPolicy::Merge`].
+#[derive(Debug)]
+pub(super) struct AssetSource {
+    pub(super) key: &'static str,
+    states: Vec<&'static str>,
+    /// Only the first file of a collection decides its start state.
+    sets_start_state: bool,
+}
+
+/// Collection of a source which is checked but not added yet.
+struct PreparedSource {
+    source: SourceId,
+    key: &'static str,
+    states: Vec<&'static str>,
+    collection: AnimationCollection,
+}
+
+impl AllAnimationResource {
+    pub fn add_from_asset<'a>(
+        &mut self,
+        animations: Handle<AnimationAssets>,
+        image: Handle<Image>,
+        asset_atlases: &mut Assets<TextureAtlas>,
+        asset_animation: &Assets<AnimationAssets>,
+        key: Option<impl Into<TextLike<'a>>>,
+    ) -> AnimationResult<&mut Self> {
+        let Some(animations_loaded) = asset_animation.get(&animations) else {
+            return Err(AnimationError::AnimationNotLoadedYet);
+        };
+
+        let static_key = {
+            let namespace = animations
+                .path()
+                .map(namespace::namespace_from_asset_path)
+                .unwrap_or_default();
+            let from_path = animations.path().and_then(namespace::name_from_asset_path);
+            let key: TextLike = match (animations_loaded.name(), key, from_path) {
+                (_, Some(from_func_call), _) => from_func_call.into(),
+                (Some(from_asset), _, _) => TextLike::Owned(from_asset.to_string()),
+                (None, None, Some(from_path)) => {
+                    TextLike::Owned(namespace::join_namespace(&namespace, &from_path))
+                }
+                _ => {
+                    return Err(AnimationError::NoSeqeunceKeyProvided);
+                }
+            };
+            key.into_registered_name()
+        };
+
+        let source = SourceId {
+            file: animations.id().untyped(),
+            entry: None,
+        };
+        self.add_source(source, static_key, animations_loaded, image, asset_atlases)?;
+
+        Ok(self)
+    }
+
+    /// Adds every collection of a file with several collections.
+    /// Each one is registered under its name inside the namespace of the file.
+    /// Its sprite sheet is loaded from its `image` path.
+    /// No sprite sheet is needed if every state is made of `images`.
+    /// # Errors
+    /// Returns an error if any collection of the file is invalid. No collection is added then.
+    pub fn add_from_multi_asset(
+        &mut self,
+        animations: Handle<AnimationCollectionsAssets>,
+        asset_server: &AssetServer,
+        asset_atlases: &mut Assets<TextureAtlas>,
+        asset_animations: &Assets<AnimationCollectionsAssets>,
+    ) -> AnimationResult<&mut Self> {
+        let Some(animations_loaded) = asset_animations.get(&animations) else {
+            return Err(AnimationError::AnimationNotLoadedYet);
+        };
+        let namespace = animations
+            .path()
+            .map(namespace::namespace_from_asset_path)
+            .unwrap_or_default();
+        let entries = animations_loaded
+            .iter()
+            .map(|(name, collection)| {
+                self.prepare_multi_entry(
+                    animations.id(),
+                    &namespace,
+                    name,
+                    collection,
+                    asset_server,
+                    asset_atlases,
+                )
+            })
+            .collect::<AnimationResult<Vec<_>>>()?;
+        for entry in entries {
+            self.register_source(entry)?;
+        }
+        Ok(self)
+    }
+
+    pub fn replace_from_assets(
+        &mut self,
+        animations_id: &AssetId<AnimationAssets>,
+        assets_animations: &Assets<AnimationAssets>,
+    ) -> AnimationResult<&mut Self> {
+        if let Some(animations) = assets_animations.get(*animations_id) {
+            let source = SourceId {
+                file: animations_id.untyped(),
+                entry: None,
+            };
+            self.reload_source(source, animations)?;
+        }
+        Ok(self)
+    }
+
+    /// Reloads every collection of a file with several collections on its own.
+    /// Collections which are new in the file are added.
+    /// The states of collections which were removed from the file are removed.
+    /// A collection is removed as a whole if no other file added states to it.
+    /// # Errors
+    /// Returns the first error of a collection. Others are still reloaded.
+    pub fn replace_from_multi_assets(
+        &mut self,
+        animations_id: &AssetId<AnimationCollectionsAssets>,
+        assets_animations: &Assets<AnimationCollectionsAssets>,
+        asset_server: &AssetServer,
+        asset_atlases: &mut Assets<TextureAtlas>,
+    ) -> AnimationResult<&mut Self> {
+        let file = animations_id.untyped();
+        let Some(animations_loaded) = assets_animations.get(*animations_id) else {
+            return Ok(self);
+        };
+        if !self.asset_sources.keys().any(|source| source.file == file) {
+            return Ok(self);
+        }
+
+        let namespace = asset_server
+            .get_path(*animations_id)
+            .map(|path| namespace::namespace_from_asset_path(&path))
+            .unwrap_or_default();
+        let removed: Vec<SourceId> = self
+            .asset_sources
+            .keys()
+            .filter(|source| source.file == file)
+            .filter(|source| {
+                source
+                    .entry
+                    .is_none_or(|entry| animations_loaded.get(entry).is_none())
+            })
+            .copied()
+            .collect();
+        for source in removed {
+            self.remove_source(source);
+        }
+
+        let mut first_error = None;
+        for (name, animations) in animations_loaded.iter() {
+            let source = SourceId {
+                file,
+                entry: Some(TextLike::Ref(name).into_registered_name()),
+            };
+            let result = if self.asset_sources.contains_key(&source) {
+                self.reload_source(source, animations)
+            } else {
+                self.prepare_multi_entry(
+                    *animations_id,
+                    &namespace,
+                    name,
+                    animations,
+                    asset_server,
+                    asset_atlases,
+                )
+                .and_then(|entry| self.register_source(entry))
+            };
+            if let Err(error) = result {
+                match first_error {
+                    None => first_error = Some(error),
+                    Some(_) => error!("Reloading animations ({}) failed: {}", name, error),
+                }
+            }
+        }
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(self),
+        }
+    }
+
+    /// Assets of all files the collection under `key` was loaded from.
+    pub fn source_assets(&self, key: &str) -> Vec<UntypedAssetId> {
+        self.asset_sources
+            .iter()
+            .filter(|(_, source)| source.key == key)
+            .map(|(id, _)| id.file)
+            .collect()
+    }
+
+    /// Names of the states which came from the file `animations`.
+    pub fn states_from_asset(&self, animations: impl Into<UntypedAssetId>) -> Vec<&'static str> {
+        let file = animations.into();
+        self.asset_sources
+            .iter()
+            .filter(|(id, _)| id.file == file)
+            .flat_map(|(_, source)| source.states.iter().copied())
+            .collect()
+    }
+
+    /// Builds the collection of one entry of a file with several collections
+    /// and checks that it can be added without adding it yet.
+    fn prepare_multi_entry(
+        &mut self,
+        file: AssetId<AnimationCollectionsAssets>,
+        namespace: &str,
+        name: &str,
+        animations: &AnimationAssets,
+        asset_server: &AssetServer,
+        asset_atlases: &mut Assets<TextureAtlas>,
+    ) -> AnimationResult<PreparedSource> {
+        let key =
+            TextLike::Owned(namespace::join_namespace(namespace, name)).into_registered_name();
+        let image = match animations.image() {
+            Some(image) => asset_server.load(image.to_string()),
+            None if animations.only_images() => Handle::default(),
+            None => return Err(AnimationError::NoImageProvided(key.to_string())),
+        };
+        let source = SourceId {
+            file: file.untyped(),
+            entry: Some(TextLike::Ref(name).into_registered_name()),
+        };
+        let prepared = self.prepare_source(source, key, animations, image, asset_atlases)?;
+        self.check_insert(key, &prepared.collection)?;
+        Ok(prepared)
+    }
+
+    fn add_source(
+        &mut self,
+        source: SourceId,
+        key: &'static str,
+        animations: &AnimationAssets,
+        image: Handle<Image>,
+        asset_atlases: &mut Assets<TextureAtlas>,
+    ) -> AnimationResult {
+        let prepared = self.prepare_source(source, key, animations, image, asset_atlases)?;
+        self.register_source(prepared)
+    }
+
+    fn prepare_source(
+        &mut self,
+        source: SourceId,
+        key: &'static str,
+        animations: &AnimationAssets,
+        image: Handle<Image>,
+        asset_atlases: &mut Assets<TextureAtlas>,
+    ) -> AnimationResult<PreparedSource> {
+        let collection = animations.to_shared_animaton_collection(
+            image,
+            asset_atlases,
+            &mut self.atlas_cache,
+            self.global_animation_duration,
+        )?;
+        collection.get_frames_under(collection.start_state())?;
+        Ok(PreparedSource {
+            source,
+            key,
+            states: animations.state_names().collect(),
+            collection,
+        })
+    }
+
+    fn register_source(&mut self, prepared: PreparedSource) -> AnimationResult {
+        let PreparedSource {
+            source,
+            key,
+            states,
+            collection,
+        } = prepared;
+        let key_existed = self.animation_seqs.contains_key(key);
+        self.insert_collection(key, collection)?;
+
+        let replaced = key_existed && self.duplicate_key_policy == DuplicateKeyPolicy::Replace;
+        if replaced {
+            self.asset_sources
+                .retain(|_, registered| registered.key != key);
+        }
+        self.asset_sources.insert(
+            source,
+            AssetSource {
+                key,
+                states: Vec::new(),
+                sets_start_state: !key_existed || replaced,
+            },
+        );
+        self.claim_states(source, states);
+        Ok(())
+    }
+
+    /// Removes the states of `source_id` from its collection.
+    /// The whole collection is removed if no other file added states to it.
+    fn remove_source(&mut self, source_id: SourceId) {
+        let Some(source) = self.asset_sources.remove(&source_id) else {
+            return;
+        };
+        let is_last_source = !self
+            .asset_sources
+            .values()
+            .any(|other| other.key == source.key);
+        if is_last_source {
+            self.remove_animations(source.key);
+            info!(
+                "Animations under key ({}) are removed with their file",
+                source.key
+            );
+        } else if let Some(collection) = self.animation_seqs.get_mut(source.key) {
+            collection.replace_states(&source.states, Default::default());
+        }
+    }
+
+    fn reload_source(
+        &mut self,
+        source_id: SourceId,
+        animations: &AnimationAssets,
+    ) -> AnimationResult {
+        let Some(source) = self.asset_sources.get(&source_id) else {
+            return Ok(());
+        };
+        let (name, sets_start_state) = (source.key, source.sets_start_state);
+
+        let new_seq = animations.to_ani_seq(self.global_animation_duration)?;
+        let start_state = TextLike::Ref(animations.start_state()).into_registered_name();
+        if sets_start_state && !new_seq.contains_key(start_state) {
+            return Err(NotFoundError::SingleAnimation(start_state.to_string()).into());
+        }
+        let new_states = new_seq.keys().copied().collect();
+        let to_change = self
+            .animation_seqs
+            .get_mut(name)
+            .ok_or_else(|| NotFoundError::AnimationSequence(name.to_string()))?;
+        to_change.replace_states(&source.states, new_seq);
+        if sets_start_state {
+            to_change.set_start_state(start_state)?;
+        }
+        for (name, set) in animations.direction_sets() {
+            to_change.insert_direction_set(name, set);
+        }
+        for (name, set) in animations.blend_sets() {
+            to_change.insert_blend_set(name, set);
+        }
+        self.claim_states(source_id, new_states);
+        Ok(())
+    }
+
+    /// Makes `source_id` the only source of `states` within its collection.
+    fn claim_states(&mut self, source_id: SourceId, states: Vec<&'static str>) {
+        let Some(key) = self.asset_sources.get(&source_id).map(|source| source.key) else {
+            return;
+        };
+        for (id, source) in self.asset_sources.iter_mut() {
+            if source.key == key && *id != source_id {
+                source.states.retain(|state| !states.contains(state));
+            }
+        }
+        if let Some(source) = self.asset_sources.get_mut(&source_id) {
+            source.states = states;
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use bevy::ecs::system::SystemState;
+
+    use super::*;
+    use crate::test_app;
+
+    const GOBLIN: &str = r#""goblin": {
+        image: Some("goblin.png"),
+        start_state: "Idle",
+        frames: [(name: "Idle", start_row: 0), (name: "Walk", start_row: 1)],
+        rows: 4,
+        columns: 4,
+        cell_size: (16, 16),
+    },"#;
+    const ORC: &str = r#""orc": {
+        image: Some("orc.png"),
+        start_state: "Idle",
+        frames: [(name: "Idle", start_row: 0)],
+        rows: 2,
+        columns: 4,
+        cell_size: (32, 32),
+    },"#;
+
+    fn file(entries: &[&str]) -> AnimationCollectionsAssets {
+        ron::from_str(&format!("{{{}}}", entries.concat())).unwrap()
+    }
+
+    type Resources<'w> = (
+        ResMut<'w, AllAnimationResource>,
+        Res<'w, AssetServer>,
+        ResMut<'w, Assets<TextureAtlas>>,
+        Res<'w, Assets<AnimationCollectionsAssets>>,
+    );
+
+    fn with_resources<R>(
+        app: &mut App,
+        run: impl FnOnce(
+            &mut AllAnimationResource,
+            &AssetServer,
+            &mut Assets<TextureAtlas>,
+            &Assets<AnimationCollectionsAssets>,
+        ) -> R,
+    ) -> R {
+        let mut state = SystemState::<Resources>::new(&mut app.world);
+        let (mut repos, server, mut atlases, files) = state.get_mut(&mut app.world);
+        run(&mut repos, &server, &mut atlases, &files)
+    }
+
+    fn add_file(
+        app: &mut App,
+        file: AnimationCollectionsAssets,
+    ) -> (Handle<AnimationCollectionsAssets>, AnimationResult) {
+        let handle = app
+            .world
+            .resource_mut::<Assets<AnimationCollectionsAssets>>()
+            .add(file);
+        let result = with_resources(app, |repos, server, atlases, files| {
+            repos
+                .add_from_multi_asset(handle.clone(), server, atlases, files)
+                .map(|_| ())
+        });
+        (handle, result)
+    }
+
+    fn keys(app: &App) -> Vec<&'static str> {
+        let mut keys: Vec<_> = app
+            .world
+            .resource::<AllAnimationResource>()
+            .collection_keys()
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn adds_every_collection_of_file() {
+        let mut app = test_app::app();
+        let (handle, result) = add_file(&mut app, file(&[GOBLIN, ORC]));
+        result.unwrap();
+
+        assert_eq!(vec!["goblin", "orc"], keys(&app));
+        let repos = app.world.resource::<AllAnimationResource>();
+        let mut states = repos.states_from_asset(handle.id());
+        states.sort_unstable();
+        assert_eq!(vec!["Idle", "Idle", "Walk"], states);
+        assert_eq!(
+            Vec2::splat(32.),
+            repos.animation_under("orc").unwrap().grid().cell_size()
+        );
+    }
+
+    #[test]
+    fn reports_source_file_in_info() {
+        let mut app = test_app::app();
+        let (handle, result) = add_file(&mut app, file(&[GOBLIN]));
+        result.unwrap();
+
+        let info = app
+            .world
+            .resource::<AllAnimationResource>()
+            .collection_info("goblin")
+            .unwrap();
+        assert_eq!(vec![handle.id().untyped()], info.sources);
+    }
+
+    #[test]
+    fn adds_nothing_if_one_collection_is_invalid() {
+        let mut app = test_app::app();
+        let without_image = ORC.replace(r#"image: Some("orc.png"),"#, "");
+        let (_, result) = add_file(&mut app, file(&[GOBLIN, &without_image]));
+        assert!(matches!(result, Err(AnimationError::NoImageProvided(key)) if key == "orc"));
+        assert!(keys(&app).is_empty());
+
+        let without_start = ORC.replace(r#"start_state: "Idle""#, r#"start_state: "Run""#);
+        let (_, result) = add_file(&mut app, file(&[GOBLIN, &without_start]));
+        assert!(result.is_err());
+        assert!(keys(&app).is_empty());
+    }
+
+    #[test]
+    fn adds_collection_of_separate_images_without_image() {
+        let mut app = test_app::app();
+        let boss = r#""boss": {
+            start_state: "Idle",
+            frames: [(name: "Idle", images: Some(List(["boss/idle_0.png", "boss/idle_1.png"])))],
+            rows: 1,
+            columns: 1,
+            cell_size: (256, 256),
+        },"#;
+        let (_, result) = add_file(&mut app, file(&[boss]));
+        result.unwrap();
+
+        assert_eq!(vec!["boss"], keys(&app));
+        let repos = app.world.resource::<AllAnimationResource>();
+        assert_eq!(
+            Handle::default(),
+            repos.animation_under("boss").unwrap().atlas()
+        );
+        assert_eq!(0, app.world.resource::<Assets<TextureAtlas>>().len());
+    }
+
+    #[test]
+    fn reload_adds_changes_and_removes_collections() {
+        let mut app = test_app::app();
+        let (handle, result) = add_file(&mut app, file(&[GOBLIN, ORC]));
+        result.unwrap();
+
+        let goblin = GOBLIN.replace(
+            r#"(name: "Walk", start_row: 1)"#,
+            r#"(name: "Run", start_row: 2)"#,
+        );
+        let troll = ORC.replace("orc", "troll");
+        app.world
+            .resource_mut::<Assets<AnimationCollectionsAssets>>()
+            .insert(handle.id(), file(&[&goblin, &troll]));
+        with_resources(&mut app, |repos, server, atlases, files| {
+            repos
+                .replace_from_multi_assets(&handle.id(), files, server, atlases)
+                .map(|_| ())
+        })
+        .unwrap();
+
+        assert_eq!(vec!["goblin", "troll"], keys(&app));
+        let repos = app.world.resource::<AllAnimationResource>();
+        let mut states: Vec<_> = repos.state_names_under("goblin").collect();
+        states.sort_unstable();
+        assert_eq!(vec!["Idle", "Run"], states);
+        assert!(repos.source_assets("orc").is_empty());
+    }
+
+    #[test]
+    fn removing_collection_on_reload_keeps_others_animating() {
+        use std::time::Duration;
+
+        use crate::{
+            animation_comp::AnimationComp, collection_look_up::CollectionId,
+            AnimationCollectionChanged, AnimationLifecycle, LifecycleKind, ListenLifecycle,
+            StateChange,
+        };
+
+        let mut app = test_app::app();
+        let (handle, result) = add_file(&mut app, file(&[ORC, GOBLIN]));
+        result.unwrap();
+        let spawn = |app: &mut App, key: &str| {
+            let bundle = app
+                .world
+                .resource::<AllAnimationResource>()
+                .create_sprite_comp(key)
+                .unwrap();
+            app.world.spawn(bundle).id()
+        };
+        let orc = spawn(&mut app, "orc");
+        app.world.entity_mut(orc).insert(ListenLifecycle::default());
+        let goblin = spawn(&mut app, "goblin");
+        test_app::tick(&mut app, Duration::ZERO);
+        test_app::drain_events::<AnimationLifecycle>(&mut app);
+
+        app.world
+            .resource_mut::<Assets<AnimationCollectionsAssets>>()
+            .insert(handle.id(), file(&[GOBLIN]));
+        with_resources(&mut app, |repos, server, atlases, files| {
+            repos
+                .replace_from_multi_assets(&handle.id(), files, server, atlases)
+                .map(|_| ())
+        })
+        .unwrap();
+        test_app::drain_events::<AnimationCollectionChanged>(&mut app);
+        test_app::tick(&mut app, Duration::from_millis(400));
+
+        let changed = test_app::drain_events::<AnimationCollectionChanged>(&mut app);
+        assert!(changed.iter().any(|changed| {
+            changed.collection == CollectionId::Key("orc")
+                && matches!(changed.changes.as_slice(), [StateChange::Removed("Idle")])
+        }));
+        let goblin = app.world.get::<TextureAtlasSprite>(goblin).unwrap();
+        assert_eq!(1, goblin.index);
+        assert!(app.world.get::<AnimationComp>(orc).is_none());
+        let lifecycle = test_app::drain_events::<AnimationLifecycle>(&mut app);
+        assert!(matches!(
+            lifecycle.as_slice(),
+            [AnimationLifecycle { who, kind: LifecycleKind::CollectionChanged, .. }] if *who == orc
+        ));
+    }
+}
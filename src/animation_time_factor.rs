@@ -18,6 +18,18 @@ pub struct AnimationTimeScale(pub PosScaleFactor);
 impl AnimationTimeScale {
     pub fn scale_duration(&self, duration: Duration) -> Duration {
         let scale: f32 = self.0.into();
-        Duration::from_secs_f32(scale * duration.as_secs_f32())
+        duration.mul_f64(scale.into())
+    }
+
+    /// Real time it takes to play `duration` of an animation at this scale.
+    /// Saturates at [`Duration::MAX`] for a scale of zero or one too small to represent the result.
+    pub fn real_duration(&self, duration: Duration) -> Duration {
+        let scale: f32 = self.0.into();
+        if duration.is_zero() {
+            Duration::ZERO
+        } else {
+            Duration::try_from_secs_f64(duration.as_secs_f64() / f64::from(scale))
+                .unwrap_or(Duration::MAX)
+        }
     }
 }
@@ -0,0 +1,88 @@
+use crate::{text_like::TextLike, PosScaleFactor};
+
+/// Range of a [`BlendSet`] which starts at the parameter value `from`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlendRange {
+    pub state: &'static str,
+    pub from: f32,
+    /// Parameter value at which the state plays at its normal speed.
+    /// The time scale grows and shrinks proportionally to the parameter around it.
+    pub reference: Option<f32>,
+}
+
+/// Picks one of several states like idle, walk and run from a float parameter like the speed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlendSet {
+    /// Sorted by `from`.
+    ranges: Vec<BlendRange>,
+}
+
+impl BlendSet {
+    /// Adds a range which starts at `from` and ends at the start of the next range.
+    pub fn with_range<'a>(
+        mut self,
+        state: impl Into<TextLike<'a>>,
+        from: f32,
+        reference: Option<f32>,
+    ) -> Self {
+        let range = BlendRange {
+            state: state.into().into_registered_name(),
+            from,
+            reference,
+        };
+        let index = self.ranges.partition_point(|other| other.from <= from);
+        self.ranges.insert(index, range);
+        self
+    }
+
+    pub fn ranges(&self) -> &[BlendRange] {
+        &self.ranges
+    }
+
+    pub fn contains_state(&self, state: &str) -> bool {
+        self.ranges.iter().any(|range| range.state == state)
+    }
+
+    /// Range whose start is the greatest one not above `parameter`.
+    /// A parameter below all ranges selects the first range.
+    pub fn select(&self, parameter: f32) -> Option<&BlendRange> {
+        let index = self.ranges.partition_point(|range| range.from <= parameter);
+        self.ranges.get(index.saturating_sub(1))
+    }
+}
+
+impl BlendRange {
+    /// Time scale which matches `parameter` with the reference of this range.
+    /// Is the normal speed for a range without reference.
+    pub fn time_scale(&self, parameter: f32) -> PosScaleFactor {
+        match self.reference {
+            Some(reference) if reference > 0. => {
+                PosScaleFactor::at_least_zero(parameter / reference)
+            }
+            _ => PosScaleFactor::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn selects_range_and_scales_time() {
+        let set = BlendSet::default()
+            .with_range("Run", 250., Some(300.))
+            .with_range("Idle", 0., None)
+            .with_range("Walk", 10., Some(100.));
+
+        assert_eq!("Idle", set.select(-5.).unwrap().state);
+        assert_eq!("Idle", set.select(9.9).unwrap().state);
+        let walk = set.select(50.).unwrap();
+        assert_eq!("Walk", walk.state);
+        assert_eq!(0.5, walk.time_scale(50.).to_f32());
+        let run = set.select(600.).unwrap();
+        assert_eq!("Run", run.state);
+        assert_eq!(2., run.time_scale(600.).to_f32());
+        assert_eq!(1., set.select(0.).unwrap().time_scale(0.).to_f32());
+    }
+}
@@ -1,22 +1,56 @@
-use bevy::{log::LogPlugin, prelude::*, sprite::SpritePlugin, time::TimePlugin};
+use bevy::{
+    log::LogPlugin, prelude::*, sprite::SpritePlugin, time::TimePlugin, transform::TransformSystem,
+};
 
 use crate::{
+    animation_collection::AnimationCollection,
     animation_respo_resource::AllAnimationResource,
+    frame_target::{FrameTarget, ImageFrame, SpriteRectFrame},
     listen_animation_end::ListenAnimationEnd,
     prelude::AnimationComp,
-    systems::{animate, apply_pending_states, do_pending_resets},
-    AnimationEnded, AnimationPrecentProgress, AnimationTimeScale, PercentScaleFactor,
-    PosScaleFactor,
+    systems::{
+        animate, apply_end_behaviors, apply_pending_states,
+        collection_changes::{
+            propagate_collection_changes, send_collection_changes, take_collection_changes,
+            PendingCollectionChanges,
+        },
+        do_pending_resets,
+        frame_boxes::{draw_animation_boxes, update_animation_boxes},
+        frame_metadata::update_frame_metadata,
+        frame_sockets::follow_sockets,
+        frame_tags::report_frame_tags,
+        image_frames::swap_frame_images,
+        prune_atlas_cache, report_pause_changes, report_started_animations,
+        sprite_rects::write_sprite_rects,
+    },
+    AnimationBoxGizmos, AnimationCollectionChanged, AnimationEnded, AnimationFellBackToStart,
+    AnimationLifecycle, AnimationPaused, AnimationPrecentProgress, AnimationResumed,
+    AnimationTimeScale, FrameMetadata, FrameTagEntered, FrameTagExited, ListenLifecycle,
+    PauseAnimation, PercentScaleFactor, PosScaleFactor,
 };
 
 #[cfg(feature = "assets")]
-use crate::{save_load::AnimationAssets, systems::asset_handling};
+use crate::{
+    save_load::{AnimationAssets, AnimationCollectionsAssets},
+    systems::asset_handling,
+};
 #[cfg(feature = "assets")]
 use bevy_common_assets::ron::RonAssetPlugin;
 
 #[derive(Default)]
 pub struct BoolAnimationPlugin;
 
+/// Groups the systems of every [`FrameTarget`] so they can be ordered against each other.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum AnimationSystems {
+    /// Applies changed collections to the entities using them.
+    CollectionChanges,
+    /// Changes the current frame.
+    Advance,
+    /// Reads the current frame.
+    FrameData,
+}
+
 #[cfg(feature = "bevy_inspect")]
 mod bevy_inspector;
 
@@ -28,26 +62,105 @@ impl Plugin for BoolAnimationPlugin {
         assert_added_plugin::<LogPlugin>(app);
         assert_added_plugin::<AssetPlugin>(app);
 
-        app.init_resource::<AllAnimationResource>()
-            .register_type::<AnimationTimeScale>()
-            .register_type::<AnimationComp>()
-            .register_type::<PosScaleFactor>()
-            .register_type::<PercentScaleFactor>()
-            .register_type::<AnimationPrecentProgress>()
-            .register_type::<ListenAnimationEnd>()
-            .add_event::<AnimationEnded>()
-            .add_systems(Update, (apply_pending_states, animate, do_pending_resets));
-
-        #[cfg(feature = "bevy_inspect")]
-        bevy_inspector::setup_bevy_inspect(app);
-        #[cfg(feature = "assets")]
-        {
-            app.add_plugins(RonAssetPlugin::<AnimationAssets>::new(&["animations.ron"]));
-            asset_handling::regisiter_systems(app);
-        };
+        add_animation(app);
     }
 }
 
+/// Everything [`BoolAnimationPlugin`] adds, without checking for the plugins it needs.
+pub(crate) fn add_animation(app: &mut App) {
+    app.init_resource::<AllAnimationResource>()
+        .init_resource::<PendingCollectionChanges>()
+        .init_asset::<AnimationCollection>()
+        .register_type::<AnimationTimeScale>()
+        .register_type::<AnimationComp>()
+        .register_type::<PosScaleFactor>()
+        .register_type::<PercentScaleFactor>()
+        .register_type::<AnimationPrecentProgress>()
+        .register_type::<ListenAnimationEnd>()
+        .register_type::<ListenLifecycle>()
+        .register_type::<PauseAnimation>()
+        .register_type::<FrameMetadata>()
+        .add_event::<AnimationEnded>()
+        .add_event::<AnimationLifecycle>()
+        .add_event::<AnimationCollectionChanged>()
+        .add_event::<AnimationFellBackToStart>()
+        .add_event::<AnimationPaused>()
+        .add_event::<AnimationResumed>()
+        .add_event::<FrameTagEntered>()
+        .add_event::<FrameTagExited>()
+        .add_systems(
+            Update,
+            (
+                take_collection_changes.before(AnimationSystems::CollectionChanges),
+                send_collection_changes.after(AnimationSystems::CollectionChanges),
+            ),
+        )
+        .add_systems(
+            Update,
+            (write_sprite_rects, swap_frame_images).in_set(AnimationSystems::FrameData),
+        )
+        .configure_sets(
+            Update,
+            (
+                AnimationSystems::CollectionChanges.before(AnimationSystems::Advance),
+                AnimationSystems::FrameData.after(AnimationSystems::Advance),
+            ),
+        )
+        .add_systems(
+            PostUpdate,
+            draw_animation_boxes
+                .after(TransformSystem::TransformPropagate)
+                .run_if(resource_exists::<AnimationBoxGizmos>()),
+        )
+        .add_systems(Last, (prune_atlas_cache, report_pause_changes));
+
+    add_frame_target_systems::<TextureAtlasSprite>(app);
+    add_frame_target_systems::<UiTextureAtlasImage>(app);
+    add_frame_target_systems::<SpriteRectFrame>(app);
+    add_frame_target_systems::<ImageFrame>(app);
+
+    #[cfg(feature = "bevy_inspect")]
+    bevy_inspector::setup_bevy_inspect(app);
+    #[cfg(feature = "assets")]
+    {
+        app.add_plugins((
+            RonAssetPlugin::<AnimationAssets>::new(&["animations.ron"]),
+            RonAssetPlugin::<AnimationCollectionsAssets>::new(&["collections.ron"]),
+        ));
+        asset_handling::regisiter_systems(app);
+    };
+}
+
+fn add_frame_target_systems<T: FrameTarget>(app: &mut App) {
+    app.add_systems(
+        Update,
+        propagate_collection_changes::<T>.in_set(AnimationSystems::CollectionChanges),
+    )
+    .add_systems(
+        Update,
+        (
+            (
+                apply_pending_states::<T>.before(animate::<T>),
+                animate::<T>,
+                do_pending_resets::<T>,
+            ),
+            apply_end_behaviors::<T>.after(animate::<T>),
+            report_started_animations::<T>.before(animate::<T>),
+        )
+            .in_set(AnimationSystems::Advance),
+    )
+    .add_systems(
+        Update,
+        (
+            update_frame_metadata::<T>,
+            report_frame_tags::<T>,
+            // Frames made of separate images need the handle of their current image.
+            (update_animation_boxes::<T>, follow_sockets::<T>).after(swap_frame_images),
+        )
+            .in_set(AnimationSystems::FrameData),
+    );
+}
+
 fn assert_added_plugin<T: Plugin>(app: &mut App) {
     assert!(
         app.is_plugin_added::<T>(),
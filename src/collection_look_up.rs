@@ -0,0 +1,134 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    animation_collection::AnimationCollection,
+    animation_comp::AnimationComp,
+    animation_error::NotFoundError,
+    animation_respo_resource::AllAnimationResource,
+    types::{AnimationIndex, KeyLookUpResult},
+};
+
+/// Identifies a collection either by its key inside [`AllAnimationResource`]
+/// or by the id of its asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CollectionId {
+    Key(&'static str),
+    Asset(AssetId<AnimationCollection>),
+}
+
+impl std::fmt::Display for CollectionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CollectionId::Key(key) => write!(f, "{}", key),
+            CollectionId::Asset(id) => write!(f, "{:?}", id),
+        }
+    }
+}
+
+/// Resolves the animation collection an [`AnimationComp`] refers to.
+/// A component either references a key inside [`AllAnimationResource`]
+/// or a handle to an [`AnimationCollection`] asset.
+pub trait CollectionLookUp {
+    fn collection_for<'a>(
+        &'a self,
+        animator: &AnimationComp,
+    ) -> KeyLookUpResult<&'a AnimationCollection>;
+}
+
+impl CollectionLookUp for AllAnimationResource {
+    fn collection_for<'a>(
+        &'a self,
+        animator: &AnimationComp,
+    ) -> KeyLookUpResult<&'a AnimationCollection> {
+        match animator.collection_handle() {
+            Some(handle) => Err(not_found_asset(handle)),
+            None => self.animation_under(animator.sequence()),
+        }
+    }
+}
+
+impl CollectionLookUp for Assets<AnimationCollection> {
+    fn collection_for<'a>(
+        &'a self,
+        animator: &AnimationComp,
+    ) -> KeyLookUpResult<&'a AnimationCollection> {
+        match animator.collection_handle() {
+            Some(handle) => self.get(handle).ok_or_else(|| not_found_asset(handle)),
+            None => Err(NotFoundError::AnimationSequence(
+                animator.sequence().to_string(),
+            )),
+        }
+    }
+}
+
+impl<T> CollectionLookUp for Res<'_, T>
+where
+    T: CollectionLookUp + Resource,
+{
+    fn collection_for<'a>(
+        &'a self,
+        animator: &AnimationComp,
+    ) -> KeyLookUpResult<&'a AnimationCollection> {
+        T::collection_for(self, animator)
+    }
+}
+
+impl<T> CollectionLookUp for ResMut<'_, T>
+where
+    T: CollectionLookUp + Resource,
+{
+    fn collection_for<'a>(
+        &'a self,
+        animator: &AnimationComp,
+    ) -> KeyLookUpResult<&'a AnimationCollection> {
+        T::collection_for(self, animator)
+    }
+}
+
+/// Gives read access to collections registered under a key
+/// and to collections loaded as assets.
+#[derive(SystemParam)]
+pub struct AnimationCollections<'w> {
+    repos: Res<'w, AllAnimationResource>,
+    assets: Res<'w, Assets<AnimationCollection>>,
+}
+
+impl<'w> AnimationCollections<'w> {
+    pub fn repos(&self) -> &AllAnimationResource {
+        &self.repos
+    }
+
+    pub fn assets(&self) -> &Assets<AnimationCollection> {
+        &self.assets
+    }
+}
+
+impl CollectionLookUp for AnimationCollections<'_> {
+    fn collection_for<'a>(
+        &'a self,
+        animator: &AnimationComp,
+    ) -> KeyLookUpResult<&'a AnimationCollection> {
+        match animator.collection_handle() {
+            Some(_) => self.assets.collection_for(animator),
+            None => self.repos.collection_for(animator),
+        }
+    }
+}
+
+pub(crate) fn not_found_asset(handle: &Handle<AnimationCollection>) -> NotFoundError {
+    NotFoundError::CollectionAsset(format!("{:?}", handle.id()))
+}
+
+/// Component playing the start state of the loaded collection asset `collection`
+/// together with the collection and the start index, as the bundles need it.
+pub(crate) fn start_of_asset(
+    collection: Handle<AnimationCollection>,
+    assets: &Assets<AnimationCollection>,
+) -> KeyLookUpResult<(AnimationComp, &AnimationCollection, AnimationIndex)> {
+    let animations = assets
+        .get(&collection)
+        .ok_or_else(|| not_found_asset(&collection))?;
+    let frames = AnimationComp::from_asset(collection, animations.start_state(), assets)?;
+    let start_index = frames.start_index(assets)?;
+    Ok((frames, animations, start_index))
+}
@@ -0,0 +1,208 @@
+use std::f32::consts::{FRAC_PI_4, PI};
+
+use bevy::math::Vec2;
+#[cfg(feature = "assets")]
+use serde::Deserialize;
+
+use crate::{animation_frames::Mirror, text_like::TextLike};
+
+/// Compass direction of a member inside a [`DirectionSet`]. North points along positive y.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "assets", derive(Deserialize))]
+#[cfg_attr(feature = "assets", serde(rename_all = "lowercase"))]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+
+    /// Angle in radians counter-clockwise from east.
+    pub fn angle(self) -> f32 {
+        let steps_from_east = match self {
+            Direction::E => 0.,
+            Direction::NE => 1.,
+            Direction::N => 2.,
+            Direction::NW => 3.,
+            Direction::W => 4.,
+            Direction::SW => -3.,
+            Direction::S => -2.,
+            Direction::SE => -1.,
+        };
+        steps_from_east * FRAC_PI_4
+    }
+
+    /// Direction after flipping on the axes of `mirror`.
+    pub fn mirrored(self, mirror: Mirror) -> Self {
+        let flipped_x = if mirror.x { self.mirrored_x() } else { self };
+        if mirror.y {
+            flipped_x.mirrored_y()
+        } else {
+            flipped_x
+        }
+    }
+
+    /// Direction on the other side of the y axis.
+    pub fn mirrored_x(self) -> Self {
+        match self {
+            Direction::NE => Direction::NW,
+            Direction::E => Direction::W,
+            Direction::SE => Direction::SW,
+            Direction::SW => Direction::SE,
+            Direction::W => Direction::E,
+            Direction::NW => Direction::NE,
+            unchanged => unchanged,
+        }
+    }
+
+    /// Direction on the other side of the x axis.
+    pub fn mirrored_y(self) -> Self {
+        match self {
+            Direction::N => Direction::S,
+            Direction::NE => Direction::SE,
+            Direction::SE => Direction::NE,
+            Direction::S => Direction::N,
+            Direction::SW => Direction::NW,
+            Direction::NW => Direction::SW,
+            unchanged => unchanged,
+        }
+    }
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Direction::N => "n",
+            Direction::NE => "ne",
+            Direction::E => "e",
+            Direction::SE => "se",
+            Direction::S => "s",
+            Direction::SW => "sw",
+            Direction::W => "w",
+            Direction::NW => "nw",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Group of states like `walk` with one member state per direction, usually 4 or 8 of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectionSet {
+    members: Vec<(Direction, &'static str)>,
+    /// Angle in radians a new direction has to be closer than the current member
+    /// before another member is picked.
+    hysteresis: f32,
+}
+
+impl Default for DirectionSet {
+    fn default() -> Self {
+        Self {
+            members: Vec::new(),
+            hysteresis: Self::DEFAULT_HYSTERESIS_DEGREES.to_radians(),
+        }
+    }
+}
+
+impl DirectionSet {
+    pub const DEFAULT_HYSTERESIS_DEGREES: f32 = 10.;
+
+    /// Adds or replaces the member state for `direction`.
+    pub fn with_member<'a>(mut self, direction: Direction, state: impl Into<TextLike<'a>>) -> Self {
+        let state = state.into().into_registered_name();
+        self.members.retain(|(member, _)| *member != direction);
+        self.members.push((direction, state));
+        self
+    }
+
+    pub fn with_hysteresis_degrees(mut self, degrees: f32) -> Self {
+        self.hysteresis = degrees.max(0.).to_radians();
+        self
+    }
+
+    pub fn member(&self, direction: Direction) -> Option<&'static str> {
+        self.members
+            .iter()
+            .find(|(member, _)| *member == direction)
+            .map(|(_, state)| *state)
+    }
+
+    pub fn members(&self) -> impl Iterator<Item = (Direction, &'static str)> + '_ {
+        self.members.iter().copied()
+    }
+
+    pub fn contains_state(&self, state: &str) -> bool {
+        self.members.iter().any(|(_, member)| *member == state)
+    }
+
+    /// Member state whose direction is closest to `direction`.
+    /// Stays with `current` if it is a member and not further away than the hysteresis allows.
+    /// Returns `None` for a zero vector or a set without members.
+    pub fn pick(&self, direction: Vec2, current: &str) -> Option<&'static str> {
+        if direction.length_squared() <= f32::EPSILON {
+            return None;
+        }
+        let angle = direction.y.atan2(direction.x);
+        let distance = |member: Direction| {
+            let difference = (angle - member.angle()).rem_euclid(2. * PI);
+            difference.min(2. * PI - difference)
+        };
+        let (best, best_state) = self
+            .members
+            .iter()
+            .min_by(|(left, _), (right, _)| distance(*left).total_cmp(&distance(*right)))?;
+        let stays = self
+            .members
+            .iter()
+            .find(|(_, state)| *state == current)
+            .filter(|(member, _)| distance(*member) <= distance(*best) + self.hysteresis);
+        Some(stays.map_or(*best_state, |(_, state)| *state))
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    fn four_way() -> DirectionSet {
+        DirectionSet::default()
+            .with_member(Direction::N, "Top")
+            .with_member(Direction::E, "Right")
+            .with_member(Direction::S, "Bottom")
+            .with_member(Direction::W, "Left")
+    }
+
+    #[test]
+    fn picks_closest_member() {
+        let set = four_way();
+        assert_eq!(Some("Right"), set.pick(Vec2::new(1., 0.2), ""));
+        assert_eq!(Some("Top"), set.pick(Vec2::new(-0.2, 1.), ""));
+        assert_eq!(Some("Left"), set.pick(Vec2::new(-1., -0.1), ""));
+        assert_eq!(Some("Bottom"), set.pick(Vec2::new(0., -3.), "Top"));
+        assert_eq!(None, set.pick(Vec2::ZERO, "Top"));
+    }
+
+    #[test]
+    fn keeps_current_member_around_diagonals() {
+        let set = four_way();
+        let just_past_diagonal = Vec2::from_angle(50_f32.to_radians());
+        assert_eq!(Some("Right"), set.pick(just_past_diagonal, "Right"));
+        let far_past_diagonal = Vec2::from_angle(60_f32.to_radians());
+        assert_eq!(Some("Top"), set.pick(far_past_diagonal, "Right"));
+    }
+}
@@ -0,0 +1,20 @@
+use bevy::reflect::Reflect;
+#[cfg(feature = "assets")]
+use serde::Deserialize;
+
+/// What happens after the last frame of a state without repeat has ended.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "assets", derive(Deserialize))]
+pub enum EndBehavior {
+    /// Stays on the last frame.
+    #[default]
+    Hold,
+    /// Goes back to the first frame and stays there.
+    Rewind,
+    /// Hides the sprite via its `Visibility`. It stays hidden until it is made visible again.
+    Hide,
+    /// Despawns the entity with all its children.
+    Despawn,
+    /// Removes the `AnimationComp` from the entity and keeps the current frame.
+    RemoveAnimation,
+}
@@ -0,0 +1,128 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{text_like::TextLike, utils::SpriteSpace};
+
+/// Named rectangle of a single frame like a hitbox or a hurtbox.
+/// Is in pixels relative to the center of the frame with y pointing up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameBox {
+    pub name: &'static str,
+    pub rect: Rect,
+}
+
+impl FrameBox {
+    pub fn new<'a>(name: impl Into<TextLike<'a>>, rect: Rect) -> Self {
+        Self {
+            name: name.into().into_registered_name(),
+            rect,
+        }
+    }
+
+    /// Box in the local space of an entity showing the frame via `sprite`.
+    /// Takes flip, anchor and custom size of `sprite` into account.
+    /// `frame_size` is the size of the frame in its sprite sheet.
+    pub fn in_sprite_space(self, sprite: &TextureAtlasSprite, frame_size: Vec2) -> Self {
+        self.in_space(&SpriteSpace::new(sprite, frame_size))
+    }
+
+    pub(crate) fn in_space(self, space: &SpriteSpace) -> Self {
+        Self {
+            name: self.name,
+            rect: Rect::from_corners(space.point(self.rect.min), space.point(self.rect.max)),
+        }
+    }
+}
+
+/// Boxes of the current frame of an entity in its local space.
+/// Is updated by the animation systems whenever the frame, the flip or the anchor
+/// of its [`FrameTarget`](crate::prelude::FrameTarget) changes.
+#[derive(Component, Debug, Default, Clone)]
+pub struct AnimationBoxes {
+    boxes: Vec<FrameBox>,
+}
+
+impl AnimationBoxes {
+    pub fn iter(&self) -> impl Iterator<Item = &FrameBox> {
+        self.boxes.iter()
+    }
+
+    /// All boxes under `name` since a frame can have several boxes with the same name.
+    pub fn named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = Rect> + 'a {
+        self.boxes
+            .iter()
+            .filter(move |frame_box| frame_box.name == name)
+            .map(|frame_box| frame_box.rect)
+    }
+
+    /// First box under `name`.
+    pub fn get(&self, name: &str) -> Option<Rect> {
+        self.named(name).next()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boxes.is_empty()
+    }
+
+    pub(crate) fn set_if_changed(&mut self, boxes: Vec<FrameBox>) -> bool {
+        if self.boxes == boxes {
+            return false;
+        }
+        self.boxes = boxes;
+        true
+    }
+}
+
+/// Draws the [`AnimationBoxes`] of all entities via gizmos as long as this resource exists.
+#[derive(Resource, Debug, Clone)]
+pub struct AnimationBoxGizmos {
+    /// Colors of boxes by their name.
+    pub colors: HashMap<&'static str, Color>,
+    /// Color of boxes whose name has no entry in `colors`.
+    pub default_color: Color,
+}
+
+impl Default for AnimationBoxGizmos {
+    fn default() -> Self {
+        Self {
+            colors: HashMap::from([("hit", Color::RED), ("hurt", Color::GREEN)]),
+            default_color: Color::YELLOW,
+        }
+    }
+}
+
+impl AnimationBoxGizmos {
+    pub fn color(&self, name: &str) -> Color {
+        self.colors.get(name).copied().unwrap_or(self.default_color)
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use bevy::sprite::Anchor;
+
+    use super::*;
+
+    #[test]
+    fn moves_box_into_sprite_space() {
+        let frame_box = FrameBox::new(
+            "hit",
+            Rect::from_corners(Vec2::new(2., -4.), Vec2::new(6., 4.)),
+        );
+        let mut sprite = TextureAtlasSprite {
+            flip_x: true,
+            ..default()
+        };
+        let frame_size = Vec2::new(16., 16.);
+        assert_eq!(
+            Rect::from_corners(Vec2::new(-6., -4.), Vec2::new(-2., 4.)),
+            frame_box.in_sprite_space(&sprite, frame_size).rect
+        );
+
+        sprite.anchor = Anchor::BottomLeft;
+        sprite.custom_size = Some(Vec2::new(32., 32.));
+        assert_eq!(
+            Rect::from_corners(Vec2::new(4., 8.), Vec2::new(12., 24.)),
+            frame_box.in_sprite_space(&sprite, frame_size).rect
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use bevy::{prelude::*, utils::HashMap};
+#[cfg(feature = "assets")]
+use serde::Deserialize;
+
+#[cfg(feature = "bevy_inspect")]
+use bevy_inspector_egui::prelude::*;
+
+/// Value a designer attached to a frame like `invulnerable: true` or `sound_surface: "stone"`.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "assets", derive(Deserialize), serde(untagged))]
+pub enum FrameValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+impl From<bool> for FrameValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<f64> for FrameValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<&str> for FrameValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for FrameValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+/// Values of the current frame of an entity by their key.
+/// Is updated by the animation systems whenever the frame changes.
+/// Is only marked as changed if the values differ from the previous frame.
+#[derive(Debug, Default, Clone, Component, Reflect)]
+#[cfg_attr(
+    feature = "bevy_inspect",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+pub struct FrameMetadata {
+    values: HashMap<String, FrameValue>,
+}
+
+impl FrameMetadata {
+    pub fn get(&self, key: &str) -> Option<&FrameValue> {
+        self.values.get(key)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// Is false if the key is missing or is not a bool.
+    pub fn flag(&self, key: &str) -> bool {
+        matches!(self.get(key), Some(FrameValue::Bool(true)))
+    }
+
+    pub fn number(&self, key: &str) -> Option<f64> {
+        match self.get(key) {
+            Some(FrameValue::Number(number)) => Some(*number),
+            _ => None,
+        }
+    }
+
+    pub fn text(&self, key: &str) -> Option<&str> {
+        match self.get(key) {
+            Some(FrameValue::Text(text)) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FrameValue)> {
+        self.values.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    pub(crate) fn set_if_changed(&mut self, values: Option<&HashMap<String, FrameValue>>) -> bool {
+        let unchanged = match values {
+            Some(values) => self.values == *values,
+            None => self.values.is_empty(),
+        };
+        if unchanged {
+            return false;
+        }
+        self.values = values.cloned().unwrap_or_default();
+        true
+    }
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::{text_like::TextLike, utils::SpriteSpace};
+
+/// Named point of a single frame where other entities like weapons can be attached.
+/// Is in pixels relative to the center of the frame with y pointing up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameSocket {
+    pub name: &'static str,
+    pub position: Vec2,
+    /// Rotation around the z axis in radians.
+    pub rotation: f32,
+}
+
+impl FrameSocket {
+    pub fn new<'a>(name: impl Into<TextLike<'a>>, position: Vec2) -> Self {
+        Self {
+            name: name.into().into_registered_name(),
+            position,
+            rotation: 0.,
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Transform of an entity attached to this socket of a frame shown via `sprite`.
+    /// A flip of `sprite` mirrors the transform via a negative scale.
+    /// `frame_size` is the size of the frame in its sprite sheet.
+    pub fn to_transform(self, sprite: &TextureAtlasSprite, frame_size: Vec2) -> Transform {
+        self.transform_in(&SpriteSpace::new(sprite, frame_size))
+    }
+
+    pub(crate) fn transform_in(self, space: &SpriteSpace) -> Transform {
+        let rotation = if space.flip.x != space.flip.y {
+            -self.rotation
+        } else {
+            self.rotation
+        };
+        Transform::from_translation(space.point(self.position).extend(0.))
+            .with_rotation(Quat::from_rotation_z(rotation))
+            .with_scale(space.flip.extend(1.))
+    }
+}
+
+/// Moves the `Transform` of an entity to the socket `name` of the current frame of its parent.
+/// The parent needs an [`AnimationComp`](crate::prelude::AnimationComp)
+/// and any [`FrameTarget`](crate::prelude::FrameTarget).
+/// The z translation and the size of the scale are kept.
+/// Children of `bevy_ui` nodes are placed by the UI layout instead, which overrides the transform.
+/// The transform stays where it is while the current frame has no such socket.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FollowSocket {
+    pub name: &'static str,
+}
+
+impl FollowSocket {
+    pub fn new<'a>(name: impl Into<TextLike<'a>>) -> Self {
+        Self {
+            name: name.into().into_registered_name(),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use crate::{
+    text_like::TextLike,
+    types::{AnimationIndex, AnimationReference},
+};
+
+/// Named range of frames inside a state like the active frames of an attack.
+/// `from` and `to` are offsets from the first frame of the state. Both are included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameTag {
+    pub name: &'static str,
+    pub from: AnimationIndex,
+    pub to: AnimationIndex,
+}
+
+impl FrameTag {
+    pub fn new<'a>(
+        name: impl Into<TextLike<'a>>,
+        from: AnimationIndex,
+        to: AnimationIndex,
+    ) -> Self {
+        Self {
+            name: name.into().into_registered_name(),
+            from,
+            to,
+        }
+    }
+
+    pub fn contains_offset(&self, offset: AnimationIndex) -> bool {
+        (self.from..=self.to).contains(&offset)
+    }
+}
+
+/// Is sent after the sprite of an entity reached a frame inside the tag `tag`.
+#[derive(Event, Debug, Clone)]
+pub struct FrameTagEntered {
+    pub who: Entity,
+    pub state: AnimationReference,
+    pub tag: &'static str,
+}
+
+/// Is sent after the sprite of an entity left the frames of the tag `tag`.
+/// Is also sent if the state changes while the tag is active.
+#[derive(Event, Debug, Clone)]
+pub struct FrameTagExited {
+    pub who: Entity,
+    pub state: AnimationReference,
+    pub tag: &'static str,
+}
+
+/// Tags the current frame of an entity was in at the last check.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ActiveTags {
+    pub(crate) state: AnimationReference,
+    pub(crate) tags: Vec<&'static str>,
+}
+
+#[cfg(test)]
+mod testing {
+    use std::time::Duration;
+
+    use crate::{animation_frames::AnimationFrames, PercentScaleFactor};
+
+    use super::*;
+
+    #[test]
+    fn finds_frames_inside_tags() {
+        let frames = AnimationFrames::from_row(1, Duration::from_secs(1), 5, true)
+            .unwrap()
+            .with_tag(FrameTag::new("active", 1, 2))
+            .unwrap()
+            .with_tag(FrameTag::new("cancel", 2, 4))
+            .unwrap();
+        assert_eq!(Vec::<&str>::new(), frames.tags_at(5).collect::<Vec<_>>());
+        assert_eq!(
+            vec!["active", "cancel"],
+            frames.tags_at(7).collect::<Vec<_>>()
+        );
+        assert!(frames.is_in_tag("cancel", 9));
+        assert_eq!(
+            Some((
+                PercentScaleFactor::new(0.25).unwrap(),
+                PercentScaleFactor::new(0.5).unwrap()
+            )),
+            frames.tag_precents("active")
+        );
+        assert!(frames
+            .clone()
+            .with_tag(FrameTag::new("late", 3, 5))
+            .is_err());
+    }
+}
@@ -0,0 +1,207 @@
+use bevy::{prelude::*, sprite::Anchor};
+
+use crate::{
+    animation_altlas::AnimationAltlasMeta, animation_frames::Mirror, types::AnimationIndex,
+};
+
+/// Component which shows the current frame of an [`AnimationComp`](crate::prelude::AnimationComp)
+/// from a texture atlas.
+/// The animation systems run for [`TextureAtlasSprite`], [`UiTextureAtlasImage`],
+/// [`SpriteRectFrame`] and [`ImageFrame`].
+pub trait FrameTarget: Component {
+    fn index(&self) -> AnimationIndex;
+
+    fn index_mut(&mut self) -> &mut AnimationIndex;
+
+    fn flip(&self) -> Mirror;
+
+    fn set_flip(&mut self, flip: Mirror);
+
+    /// Is `None` for targets without an anchor. Anchors of states are ignored for them.
+    fn anchor(&self) -> Option<Anchor> {
+        None
+    }
+
+    fn set_anchor(&mut self, _anchor: Anchor) {}
+}
+
+impl FrameTarget for TextureAtlasSprite {
+    fn index(&self) -> AnimationIndex {
+        self.index
+    }
+
+    fn index_mut(&mut self) -> &mut AnimationIndex {
+        &mut self.index
+    }
+
+    fn flip(&self) -> Mirror {
+        Mirror {
+            x: self.flip_x,
+            y: self.flip_y,
+        }
+    }
+
+    fn set_flip(&mut self, flip: Mirror) {
+        self.flip_x = flip.x;
+        self.flip_y = flip.y;
+    }
+
+    fn anchor(&self) -> Option<Anchor> {
+        Some(self.anchor)
+    }
+
+    fn set_anchor(&mut self, anchor: Anchor) {
+        self.anchor = anchor;
+    }
+}
+
+impl FrameTarget for UiTextureAtlasImage {
+    fn index(&self) -> AnimationIndex {
+        self.index
+    }
+
+    fn index_mut(&mut self) -> &mut AnimationIndex {
+        &mut self.index
+    }
+
+    fn flip(&self) -> Mirror {
+        Mirror {
+            x: self.flip_x,
+            y: self.flip_y,
+        }
+    }
+
+    fn set_flip(&mut self, flip: Mirror) {
+        self.flip_x = flip.x;
+        self.flip_y = flip.y;
+    }
+}
+
+/// Frame target for a plain [`Sprite`] which shows a cell of a large image via [`Sprite::rect`].
+/// The rect, flips and anchor of the sprite on the same entity are set from this component
+/// whenever it changes, so flip and anchor are set here instead of on the sprite.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct SpriteRectFrame {
+    pub index: AnimationIndex,
+    pub flip: Mirror,
+    pub anchor: Anchor,
+}
+
+impl SpriteRectFrame {
+    pub fn new(index: AnimationIndex) -> Self {
+        Self { index, ..default() }
+    }
+
+    /// Sets rect, flips and anchor of `sprite` to the cell of `grid` at the current index.
+    pub(crate) fn write_to(&self, sprite: &mut Sprite, grid: &AnimationAltlasMeta) {
+        sprite.rect = Some(grid.rect_of(self.index));
+        sprite.flip_x = self.flip.x;
+        sprite.flip_y = self.flip.y;
+        sprite.anchor = self.anchor;
+    }
+}
+
+impl FrameTarget for SpriteRectFrame {
+    fn index(&self) -> AnimationIndex {
+        self.index
+    }
+
+    fn index_mut(&mut self) -> &mut AnimationIndex {
+        &mut self.index
+    }
+
+    fn flip(&self) -> Mirror {
+        self.flip
+    }
+
+    fn set_flip(&mut self, flip: Mirror) {
+        self.flip = flip;
+    }
+
+    fn anchor(&self) -> Option<Anchor> {
+        Some(self.anchor)
+    }
+
+    fn set_anchor(&mut self, anchor: Anchor) {
+        self.anchor = anchor;
+    }
+}
+
+/// Frame target for a plain [`Sprite`] whose states show separate images
+/// like [`AnimationFrames::from_images`](crate::prelude::AnimationFrames::from_images).
+/// The `Handle<Image>`, flips and anchor of the sprite on the same entity are set
+/// from this component whenever it changes.
+#[derive(Component, Debug, Clone)]
+pub struct ImageFrame {
+    pub index: AnimationIndex,
+    pub flip: Mirror,
+    pub anchor: Anchor,
+    /// Number of following frames whose images are loaded ahead.
+    pub preload: usize,
+    /// Keeps the images of the following frames loaded.
+    pub(crate) preloaded: Vec<Handle<Image>>,
+}
+
+impl Default for ImageFrame {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            flip: Mirror::default(),
+            anchor: Anchor::default(),
+            preload: 3,
+            preloaded: Vec::new(),
+        }
+    }
+}
+
+impl ImageFrame {
+    pub fn new(index: AnimationIndex) -> Self {
+        Self { index, ..default() }
+    }
+
+    pub fn with_preload(mut self, preload: usize) -> Self {
+        self.preload = preload;
+        self
+    }
+}
+
+impl FrameTarget for ImageFrame {
+    fn index(&self) -> AnimationIndex {
+        self.index
+    }
+
+    fn index_mut(&mut self) -> &mut AnimationIndex {
+        &mut self.index
+    }
+
+    fn flip(&self) -> Mirror {
+        self.flip
+    }
+
+    fn set_flip(&mut self, flip: Mirror) {
+        self.flip = flip;
+    }
+
+    fn anchor(&self) -> Option<Anchor> {
+        Some(self.anchor)
+    }
+
+    fn set_anchor(&mut self, anchor: Anchor) {
+        self.anchor = anchor;
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn writes_rect_of_grid_cell() {
+        let grid = AnimationAltlasMeta::new(2, 3, Vec2::new(16., 8.))
+            .new_padding(Vec2::new(2., 1.))
+            .new_offset(Vec2::new(4., 4.));
+        let mut sprite = Sprite::default();
+        SpriteRectFrame::new(4).write_to(&mut sprite, &grid);
+        assert_eq!(Some(Rect::new(22., 13., 38., 21.)), sprite.rect);
+    }
+}
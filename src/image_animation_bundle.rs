@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation_collection::AnimationCollection, animation_comp::AnimationComp,
+    animation_time_factor::AnimationTimeScale, collection_look_up::start_of_asset,
+    frame_target::ImageFrame, types::KeyLookUpResult,
+};
+
+/// Same as [`SpriteAnimationBundle`](crate::prelude::SpriteAnimationBundle)
+/// for states made of separate images.
+/// The image of the sprite is set by the animation systems.
+#[derive(Bundle)]
+pub struct ImageAnimationBundle {
+    pub sprite: SpriteBundle,
+    pub frame: ImageFrame,
+    pub frames: AnimationComp,
+    pub time_scale: AnimationTimeScale,
+}
+
+impl ImageAnimationBundle {
+    /// Same as [`SpriteAnimationBundle::from_asset`](crate::prelude::SpriteAnimationBundle::from_asset).
+    pub fn from_asset(
+        collection: Handle<AnimationCollection>,
+        assets: &Assets<AnimationCollection>,
+    ) -> KeyLookUpResult<Self> {
+        let (frames, animations, start_index) = start_of_asset(collection, assets)?;
+        Ok(Self::new(frames, animations, start_index))
+    }
+
+    pub(crate) fn new(
+        mut frames: AnimationComp,
+        animations: &AnimationCollection,
+        start_index: usize,
+    ) -> Self {
+        let mut frame = ImageFrame::new(start_index);
+        if let Ok(start_frames) = animations.get_frames_under(frames.current_state()) {
+            if let Some(mirror) = start_frames.mirror() {
+                mirror.apply(&mut frame);
+            }
+            frames.apply_anchor(&mut frame, &start_frames);
+        }
+        Self {
+            sprite: SpriteBundle::default(),
+            frame,
+            time_scale: Default::default(),
+            frames,
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        animation_frames::AnimationFrames,
+        animation_respo_resource::AllAnimationResource,
+        prelude::AnimationCollectionBuilder,
+        test_app::{self, KEY},
+    };
+
+    fn images(state: &str, count: usize) -> Vec<String> {
+        (0..count)
+            .map(|number| format!("{state}_{number}.png"))
+            .collect()
+    }
+
+    #[test]
+    fn swaps_and_preloads_images() {
+        let mut app = test_app::app();
+        let walk = AnimationFrames::from_images(images("walk", 4), test_app::ROW_TIME, true);
+        let jump = AnimationFrames::from_images(images("jump", 2), test_app::ROW_TIME, false);
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_animation("Walk", walk.unwrap())
+            .add_animation("Jump", jump.unwrap())
+            .build("Walk");
+        test_app::add_collection(&mut app, collection);
+        let bundle = app
+            .world
+            .resource::<AllAnimationResource>()
+            .create_image_comp(KEY)
+            .unwrap();
+        let sprite = app.world.spawn(bundle).id();
+        let load = |app: &App, paths: &[&str]| -> Vec<Handle<Image>> {
+            let server = app.world.resource::<AssetServer>();
+            paths
+                .iter()
+                .map(|path| server.load(path.to_string()))
+                .collect()
+        };
+        let shown = |app: &App| {
+            let image = app.world.get::<Handle<Image>>(sprite).unwrap().clone();
+            let preloaded = app
+                .world
+                .get::<ImageFrame>(sprite)
+                .unwrap()
+                .preloaded
+                .clone();
+            (image, preloaded)
+        };
+
+        test_app::tick(&mut app, Duration::ZERO);
+        let (image, preloaded) = shown(&app);
+        assert_eq!(load(&app, &["walk_0.png"])[0], image);
+        assert_eq!(
+            load(&app, &["walk_1.png", "walk_2.png", "walk_3.png"]),
+            preloaded
+        );
+
+        test_app::switch_to(&mut app, sprite, "Jump");
+        let (image, preloaded) = shown(&app);
+        assert_eq!(load(&app, &["jump_0.png"])[0], image);
+        assert_eq!(load(&app, &["jump_1.png"]), preloaded);
+
+        test_app::switch_to(&mut app, sprite, "Walk");
+        test_app::tick(&mut app, Duration::from_millis(250));
+        let (image, preloaded) = shown(&app);
+        assert_eq!(load(&app, &["walk_1.png"])[0], image);
+        assert_eq!(
+            load(&app, &["walk_2.png", "walk_3.png", "walk_0.png"]),
+            preloaded
+        );
+    }
+}
@@ -3,31 +3,69 @@ pub mod prelude;
 #[cfg(feature = "assets")]
 pub mod save_load;
 
+pub mod namespace;
 pub mod types;
 
+pub use animation_collection_changed::{
+    AnimationCollectionChanged, AnimationFellBackToStart, StateChange,
+};
 pub use animation_ended::AnimationEnded;
+pub use animation_frames::Mirror;
+pub use animation_lifecycle::{
+    AnimationLifecycle, LifecycleFilter, LifecycleKind, ListenLifecycle,
+};
 pub use animation_precent_progress::AnimationPrecentProgress;
 pub use animation_time_factor::AnimationTimeScale;
+pub use blend_set::{BlendRange, BlendSet};
+pub use collection_look_up::{AnimationCollections, CollectionId, CollectionLookUp};
+pub use direction_set::{Direction, DirectionSet};
+pub use end_behavior::EndBehavior;
+pub use frame_boxes::{AnimationBoxGizmos, AnimationBoxes, FrameBox};
+pub use frame_metadata::{FrameMetadata, FrameValue};
+pub use frame_sockets::{FollowSocket, FrameSocket};
+pub use frame_tags::{FrameTag, FrameTagEntered, FrameTagExited};
+pub use frame_target::{FrameTarget, ImageFrame, SpriteRectFrame};
 pub use listen_animation_end::ListenAnimationEnd;
+pub use pause_animation::{AnimationPaused, AnimationPauser, AnimationResumed, PauseAnimation};
 pub use pos_scale_factor::{InvalidPosScaleValue, PosScaleFactor};
 pub use precent::{InvalidScaleValue, PercentScaleFactor};
+pub use repository_inspection::{CollectionInfo, PlaybackMode, StateInfo};
 
 pub(crate) mod utils;
 
 mod animation_altlas;
 mod animation_collection;
+mod animation_collection_changed;
 mod animation_comp;
 mod animation_ended;
 mod animation_error;
 mod animation_frames;
+mod animation_lifecycle;
 mod animation_precent_progress;
 mod animation_respo_resource;
 mod animation_time_factor;
+mod blend_set;
 mod bool_animation_plugin;
+mod collection_look_up;
+mod direction_set;
+mod end_behavior;
+mod frame_boxes;
+mod frame_metadata;
+mod frame_sockets;
+mod frame_tags;
+mod frame_target;
+mod image_animation_bundle;
 mod listen_animation_end;
+mod pause_animation;
+mod per_frame;
 mod pos_scale_factor;
 mod precent;
+mod repository_inspection;
 mod sprite_animation_bundle;
+mod sprite_rect_animation_bundle;
 mod static_text_repos;
 mod systems;
+#[cfg(test)]
+mod test_app;
 mod text_like;
+mod ui_animation_bundle;
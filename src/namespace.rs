@@ -0,0 +1,85 @@
+use bevy::asset::AssetPath;
+
+/// Separates the namespaces of a collection key like `enemies/goblin/elite`.
+pub const NAMESPACE_SEPARATOR: char = '/';
+
+/// Returns true if `key` is the namespace itself or lies somewhere below it.
+/// Every key is inside the empty namespace.
+pub fn is_in_namespace(key: &str, namespace: &str) -> bool {
+    let namespace = namespace.trim_end_matches(NAMESPACE_SEPARATOR);
+    if namespace.is_empty() {
+        return true;
+    }
+    match key.strip_prefix(namespace) {
+        Some(rest) => rest.is_empty() || rest.starts_with(NAMESPACE_SEPARATOR),
+        None => false,
+    }
+}
+
+/// Puts `name` below `namespace`. An empty namespace returns `name` as it is.
+pub fn join_namespace(namespace: &str, name: &str) -> String {
+    let namespace = namespace.trim_end_matches(NAMESPACE_SEPARATOR);
+    if namespace.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}{}{}", namespace, NAMESPACE_SEPARATOR, name)
+    }
+}
+
+/// Namespace of a file is made of the folders it is located in.
+/// `enemies/goblin/elite.animations.ron` is in the namespace `enemies/goblin`.
+pub fn namespace_from_asset_path(path: &AssetPath) -> String {
+    path.path()
+        .parent()
+        .map(|parent| {
+            parent
+                .iter()
+                .map(|folder| folder.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(&NAMESPACE_SEPARATOR.to_string())
+        })
+        .unwrap_or_default()
+}
+
+/// Name of a file up to its first dot.
+/// `enemies/goblin/elite.animations.ron` has the name `elite`.
+pub fn name_from_asset_path(path: &AssetPath) -> Option<String> {
+    let file_name = path.path().file_name()?.to_string_lossy();
+    file_name
+        .split('.')
+        .next()
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+
+    #[test]
+    fn detects_keys_inside_namespace() {
+        assert!(is_in_namespace("enemies/goblin/elite", "enemies"));
+        assert!(is_in_namespace("enemies/goblin/elite", "enemies/goblin/"));
+        assert!(is_in_namespace("enemies/goblin", "enemies/goblin"));
+        assert!(is_in_namespace("player", ""));
+        assert!(!is_in_namespace("enemies/goblin_elite", "enemies/goblin"));
+        assert!(!is_in_namespace("player", "enemies"));
+    }
+
+    #[test]
+    fn namespace_and_name_from_path() {
+        let path = AssetPath::from("enemies/goblin/elite.animations.ron");
+        assert_eq!("enemies/goblin", namespace_from_asset_path(&path));
+        assert_eq!(Some("elite".to_string()), name_from_asset_path(&path));
+
+        let path = AssetPath::from("player.animations.ron");
+        assert_eq!("", namespace_from_asset_path(&path));
+        assert_eq!(
+            "player",
+            join_namespace(
+                &namespace_from_asset_path(&path),
+                &name_from_asset_path(&path).unwrap()
+            )
+        );
+    }
+}
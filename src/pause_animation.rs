@@ -0,0 +1,150 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+#[cfg(feature = "bevy_inspect")]
+use bevy_inspector_egui::prelude::*;
+
+use crate::{animation_comp::AnimationComp, collection_look_up::CollectionId};
+
+/// Freezes the animation of an entity until the component is removed.
+/// The [`AnimationTimeScale`](crate::AnimationTimeScale) of the entity stays untouched.
+#[derive(Debug, Default, Component, Reflect)]
+#[cfg_attr(
+    feature = "bevy_inspect",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
+pub struct PauseAnimation;
+
+/// Is sent after [`PauseAnimation`] was added to an entity.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AnimationPaused {
+    pub who: Entity,
+}
+
+/// Is sent after [`PauseAnimation`] was removed from an entity which still exists.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AnimationResumed {
+    pub who: Entity,
+}
+
+/// Pauses and resumes animations of single entities or of all entities using a collection.
+#[derive(SystemParam)]
+pub struct AnimationPauser<'w, 's> {
+    commands: Commands<'w, 's>,
+    animators: Query<'w, 's, (Entity, &'static AnimationComp, Has<PauseAnimation>)>,
+}
+
+impl<'w, 's> AnimationPauser<'w, 's> {
+    pub fn pause(&mut self, who: Entity) {
+        if let Ok((_, _, false)) = self.animators.get(who) {
+            self.commands.entity(who).insert(PauseAnimation);
+        }
+    }
+
+    pub fn resume(&mut self, who: Entity) {
+        if let Ok((_, _, true)) = self.animators.get(who) {
+            self.commands.entity(who).remove::<PauseAnimation>();
+        }
+    }
+
+    /// Pauses every entity which takes its states from `collection`.
+    pub fn pause_collection(&mut self, collection: &CollectionId) {
+        for (who, animator, paused) in self.animators.iter() {
+            if !paused && animator.refers_to(collection) {
+                self.commands.entity(who).insert(PauseAnimation);
+            }
+        }
+    }
+
+    /// Resumes every entity which takes its states from `collection`.
+    pub fn resume_collection(&mut self, collection: &CollectionId) {
+        for (who, animator, paused) in self.animators.iter() {
+            if paused && animator.refers_to(collection) {
+                self.commands.entity(who).remove::<PauseAnimation>();
+            }
+        }
+    }
+
+    pub fn is_paused(&self, who: Entity) -> bool {
+        matches!(self.animators.get(who), Ok((_, _, true)))
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::{
+        prelude::{AllAnimationResource, AnimationCollectionBuilder},
+        test_app::{self, KEY},
+        AnimationTimeScale, PosScaleFactor,
+    };
+
+    const OTHER: &str = "Other";
+
+    fn elapsed(app: &App, who: Entity) -> (usize, Duration) {
+        let animator = app.world.get::<AnimationComp>(who).unwrap();
+        let index = app.world.get::<TextureAtlasSprite>(who).unwrap().index;
+        (index, animator.duration_for_animation().elapsed())
+    }
+
+    #[test]
+    fn pauses_collection_and_resumes_with_kept_timer() {
+        let mut app = test_app::app();
+        for key in [KEY, OTHER] {
+            let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+                .add_row_ani("Idle", 0, test_app::ROW_TIME, true)
+                .build("Idle");
+            app.world
+                .resource_mut::<AllAnimationResource>()
+                .add_animations(key, collection);
+        }
+        let slow = test_app::spawn_sprite(&mut app);
+        let normal = test_app::spawn_sprite(&mut app);
+        let other = app
+            .world
+            .resource::<AllAnimationResource>()
+            .create_sprite_comp(OTHER)
+            .unwrap();
+        let other = app.world.spawn(other).id();
+        let half = PosScaleFactor::new(0.5).unwrap();
+        app.world.entity_mut(slow).insert(AnimationTimeScale(half));
+        test_app::tick(&mut app, Duration::ZERO);
+        test_app::tick(&mut app, Duration::from_millis(100));
+
+        app.world.run_system_once(|mut pauser: AnimationPauser| {
+            pauser.pause_collection(&CollectionId::Key(KEY));
+        });
+        test_app::tick(&mut app, Duration::ZERO);
+        let mut paused: Vec<_> = test_app::drain_events::<AnimationPaused>(&mut app)
+            .into_iter()
+            .map(|event| event.who)
+            .collect();
+        paused.sort_unstable();
+        assert_eq!(vec![slow, normal], paused);
+
+        test_app::tick(&mut app, Duration::from_secs(1));
+        assert_eq!((0, Duration::from_millis(50)), elapsed(&app, slow));
+        assert_eq!((0, Duration::from_millis(100)), elapsed(&app, normal));
+        assert_eq!((0, Duration::from_millis(100)), elapsed(&app, other));
+
+        app.world
+            .run_system_once(move |mut pauser: AnimationPauser| {
+                assert!(pauser.is_paused(slow));
+                pauser.resume(slow);
+            });
+        test_app::tick(&mut app, Duration::ZERO);
+        let resumed = test_app::drain_events::<AnimationResumed>(&mut app);
+        assert!(matches!(resumed.as_slice(), [AnimationResumed { who }] if *who == slow));
+        assert_eq!(half, app.world.get::<AnimationTimeScale>(slow).unwrap().0);
+
+        test_app::tick(&mut app, Duration::from_millis(200));
+        assert_eq!((0, Duration::from_millis(150)), elapsed(&app, slow));
+        test_app::tick(&mut app, Duration::from_millis(200));
+        assert_eq!((1, Duration::from_millis(0)), elapsed(&app, slow));
+        assert_eq!((0, Duration::from_millis(100)), elapsed(&app, normal));
+    }
+}
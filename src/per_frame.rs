@@ -0,0 +1,58 @@
+use crate::types::AnimationIndex;
+
+/// Values of single frames of a state by their offset from its first frame.
+/// Is sorted by offset.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PerFrame<T>(Vec<(AnimationIndex, T)>);
+
+impl<T> Default for PerFrame<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T> PerFrame<T> {
+    pub(crate) fn get(&self, offset: AnimationIndex) -> Option<&T> {
+        self.position(offset)
+            .ok()
+            .map(|position| &self.0[position].1)
+    }
+
+    /// `None` removes the value of the frame.
+    pub(crate) fn set(&mut self, offset: AnimationIndex, value: Option<T>) {
+        match (self.position(offset), value) {
+            (Ok(position), Some(value)) => self.0[position].1 = value,
+            (Ok(position), None) => {
+                self.0.remove(position);
+            }
+            (Err(position), Some(value)) => self.0.insert(position, (offset, value)),
+            (Err(_), None) => (),
+        }
+    }
+
+    pub(crate) fn get_or_insert_default(&mut self, offset: AnimationIndex) -> &mut T
+    where
+        T: Default,
+    {
+        let position = match self.position(offset) {
+            Ok(position) => position,
+            Err(position) => {
+                self.0.insert(position, (offset, T::default()));
+                position
+            }
+        };
+        &mut self.0[position].1
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (AnimationIndex, &T)> {
+        self.0.iter().map(|(offset, value)| (*offset, value))
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn position(&self, offset: AnimationIndex) -> Result<usize, usize> {
+        self.0.binary_search_by_key(&offset, |(frame, _)| *frame)
+    }
+}
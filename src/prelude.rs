@@ -1,11 +1,17 @@
 pub use crate::types::*;
 pub use crate::{
-    animation_altlas::{AnimationAltlas, AnimationAltlasMeta},
-    animation_collection::AnimationCollectionBuilder,
+    animation_altlas::{AnimationAltlas, AnimationAltlasMeta, TextureAtlasCache},
+    animation_collection::{AnimationCollection, AnimationCollectionBuilder},
     animation_comp::AnimationComp,
     animation_ended::AnimationEnded,
-    animation_respo_resource::AllAnimationResource,
+    animation_respo_resource::{AllAnimationResource, DuplicateKeyPolicy},
     bool_animation_plugin::BoolAnimationPlugin,
+    image_animation_bundle::ImageAnimationBundle,
+    sprite_animation_bundle::SpriteAnimationBundle,
+    sprite_rect_animation_bundle::SpriteRectAnimationBundle,
     types::AnimationDuration,
-    AnimationTimeScale, InvalidScaleValue, ListenAnimationEnd, PercentScaleFactor, PosScaleFactor,
+    ui_animation_bundle::UiAnimationBundle,
+    AnimationBoxes, AnimationCollections, AnimationTimeScale, CollectionLookUp, EndBehavior,
+    FollowSocket, FrameMetadata, FrameTarget, ImageFrame, InvalidScaleValue, ListenAnimationEnd,
+    ListenLifecycle, PauseAnimation, PercentScaleFactor, PosScaleFactor, SpriteRectFrame,
 };
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation_collection::AnimationCollection,
+    animation_frames::AnimationFrames,
+    end_behavior::EndBehavior,
+    types::{AnimationDuration, AnimationIndex},
+};
+
+#[cfg(feature = "assets")]
+use bevy::asset::UntypedAssetId;
+
+/// How a state behaves after its last frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackMode {
+    /// Starts again at the first frame.
+    Repeat,
+    /// Stops at the last frame.
+    Once,
+}
+
+/// Snapshot of a single animation state.
+#[derive(Debug, Clone)]
+pub struct StateInfo {
+    pub name: &'static str,
+    pub start: AnimationIndex,
+    pub end: AnimationIndex,
+    pub frame_count: usize,
+    pub duration: AnimationDuration,
+    pub playback: PlaybackMode,
+    /// Is only used for [`PlaybackMode::Once`].
+    pub end_behavior: EndBehavior,
+    pub atlas: Handle<TextureAtlas>,
+}
+
+impl StateInfo {
+    pub(crate) fn new(
+        name: &'static str,
+        frames: &AnimationFrames,
+        atlas: Handle<TextureAtlas>,
+    ) -> Self {
+        Self {
+            name,
+            start: frames.start(),
+            end: frames.end(),
+            frame_count: frames.frame_count(),
+            duration: frames.time(),
+            playback: if frames.is_infinite() {
+                PlaybackMode::Repeat
+            } else {
+                PlaybackMode::Once
+            },
+            end_behavior: frames.end_behavior(),
+            atlas,
+        }
+    }
+}
+
+/// Snapshot of a collection and all its states.
+#[derive(Debug, Clone)]
+pub struct CollectionInfo {
+    /// Key in [`AllAnimationResource`](crate::prelude::AllAnimationResource).
+    /// Is `None` for a collection asset.
+    pub key: Option<&'static str>,
+    pub start_state: &'static str,
+    pub atlas: Handle<TextureAtlas>,
+    /// Assets of the files this collection was loaded from.
+    #[cfg(feature = "assets")]
+    pub sources: Vec<UntypedAssetId>,
+    /// Sorted by name.
+    pub states: Vec<StateInfo>,
+}
+
+impl CollectionInfo {
+    pub(crate) fn new(key: Option<&'static str>, collection: &AnimationCollection) -> Self {
+        let mut states: Vec<StateInfo> = collection.state_infos().collect();
+        states.sort_by_key(|state| state.name);
+        Self {
+            key,
+            start_state: collection.start_state(),
+            atlas: collection.atlas(),
+            #[cfg(feature = "assets")]
+            sources: Vec::new(),
+            states,
+        }
+    }
+}
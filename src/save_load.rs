@@ -1,10 +1,18 @@
-use bevy::{prelude::*, reflect::TypePath};
-use serde::Deserialize;
+use bevy::{prelude::*, reflect::TypePath, utils::HashMap};
+use serde::{Deserialize, Deserializer};
 
 use crate::{
+    animation_altlas::{AnimationAltlas, TextureAtlasCache},
     animation_collection::{AnimationCollection, AnimationSequenceBuilder},
-    animation_error::NegativeAnimationTime,
-    animation_frames::AnimationFrames,
+    animation_error::{AnimationFrameError, NegativeAnimationTime, NotFoundError},
+    animation_frames::{AnimationFrames, Mirror},
+    blend_set::BlendSet,
+    direction_set::{Direction, DirectionSet},
+    end_behavior::EndBehavior,
+    frame_boxes::FrameBox,
+    frame_metadata::FrameValue,
+    frame_sockets::FrameSocket,
+    frame_tags::FrameTag,
     prelude::{AnimationAltlasMeta, AnimationCollectionBuilder, AnimationIndex, AnimationSequence},
     text_like::TextLike,
     types::{AnimationDuration, AnimationResult},
@@ -14,19 +22,262 @@ use crate::{
 #[derive(Deserialize, Clone, Debug)]
 pub struct FramesSerde {
     name: String,
-    start_row: usize,
+    /// Is required unless the state is made of `images`.
+    #[serde(default, deserialize_with = "present")]
+    start_row: Option<usize>,
     start_column: Option<usize>,
     end_row: Option<usize>,
     end_column: Option<usize>,
     time_secs: Option<f32>,
     #[serde(default = "true_default")]
     infinite: bool,
+    #[serde(default)]
+    end_behavior: EndBehavior,
+    /// Anchor of the sprite for all frames of this state.
+    anchor: Option<Vec2>,
+    /// Anchors of single frames by their offset from the first frame of this state.
+    #[serde(default)]
+    frame_anchors: HashMap<usize, Vec2>,
+    /// Named rectangles like hitboxes of single frames by their offset from the first frame.
+    #[serde(default)]
+    frame_boxes: HashMap<usize, Vec<FrameBoxSerde>>,
+    /// Named attachment points of single frames by their offset from the first frame.
+    #[serde(default)]
+    frame_sockets: HashMap<usize, Vec<FrameSocketSerde>>,
+    /// Values of frames like `invulnerable: true`.
+    /// Later entries overwrite the same keys of earlier ones.
+    #[serde(default)]
+    metadata: Vec<FrameMetadataSerde>,
+    /// Named ranges of frames like the active frames of an attack.
+    #[serde(default)]
+    tags: Vec<FrameTagSerde>,
+    /// Separate images as frames instead of cells of the sprite sheet.
+    images: Option<ImagesSerde>,
+}
+
+/// Asset paths of the frames of a state which is too large for a sprite sheet.
+#[derive(Deserialize, Clone, Debug)]
+pub enum ImagesSerde {
+    List(Vec<String>),
+    /// `{}` in `path` is replaced by every number from `first` up to and including `last`.
+    /// Numbers are padded with zeros to `digits` digits.
+    Pattern {
+        path: String,
+        first: usize,
+        last: usize,
+        #[serde(default)]
+        digits: usize,
+    },
+}
+
+impl ImagesSerde {
+    fn paths(&self) -> Vec<String> {
+        match self {
+            ImagesSerde::List(paths) => paths.clone(),
+            ImagesSerde::Pattern {
+                path,
+                first,
+                last,
+                digits,
+            } => (*first..=*last)
+                .map(|number| path.replacen("{}", &format!("{:0digits$}", number), 1))
+                .collect(),
+        }
+    }
+}
+
+/// Frames from offset `from` up to and including `to` counted from the first frame.
+#[derive(Deserialize, Clone, Debug)]
+pub struct FrameTagSerde {
+    name: String,
+    from: usize,
+    to: usize,
+}
+
+/// Values for the frames from offset `from` up to and including `to` counted from the first frame.
+/// Only the frame `from` gets the values without `to`.
+#[derive(Deserialize, Clone, Debug)]
+pub struct FrameMetadataSerde {
+    from: usize,
+    to: Option<usize>,
+    values: HashMap<String, FrameValue>,
+}
+
+/// Rectangle in pixels relative to the center of the frame with y pointing up.
+#[derive(Deserialize, Clone, Debug)]
+pub struct FrameBoxSerde {
+    name: String,
+    min: Vec2,
+    max: Vec2,
+}
+
+/// Point in pixels relative to the center of the frame with y pointing up.
+#[derive(Deserialize, Clone, Debug)]
+pub struct FrameSocketSerde {
+    name: String,
+    position: Vec2,
+    #[serde(default)]
+    rotation_degrees: f32,
+}
+
+impl FrameSocketSerde {
+    fn to_frame_socket(&self) -> FrameSocket {
+        FrameSocket::new(&self.name, self.position)
+            .with_rotation(self.rotation_degrees.to_radians())
+    }
+}
+
+impl FrameBoxSerde {
+    fn to_frame_box(&self) -> FrameBox {
+        FrameBox::new(&self.name, Rect::from_corners(self.min, self.max))
+    }
+}
+
+/// State which shows the frames of the state `of` flipped.
+#[derive(Deserialize, Clone, Debug)]
+pub struct MirroredSerde {
+    name: String,
+    of: String,
+    #[serde(default)]
+    flip_x: bool,
+    #[serde(default)]
+    flip_y: bool,
+}
+
+impl MirroredSerde {
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    pub fn of(&self) -> &str {
+        self.of.as_ref()
+    }
+
+    pub fn mirror(&self) -> Mirror {
+        Mirror {
+            x: self.flip_x,
+            y: self.flip_y,
+        }
+    }
+}
+
+/// Direction set like `walk` with a member state per direction.
+/// Missing directions can be filled with mirrored members of the opposite side.
+#[derive(Deserialize, Clone, Debug)]
+pub struct DirectionalSerde {
+    name: String,
+    members: HashMap<Direction, String>,
+    /// Fills missing directions with members from the other side of the y axis flipped on x.
+    #[serde(default)]
+    mirror_x: bool,
+    /// Fills missing directions with members from the other side of the x axis flipped on y.
+    #[serde(default)]
+    mirror_y: bool,
+    hysteresis_degrees: Option<f32>,
+}
+
+impl DirectionalSerde {
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    /// Members which show another member mirrored.
+    fn mirrored_members(&self) -> Vec<MirroredMember> {
+        let mut generated: Vec<MirroredMember> = Vec::new();
+        let mirrors = [
+            (self.mirror_x, Mirror { x: true, y: false }),
+            (self.mirror_y, Mirror { x: false, y: true }),
+        ];
+        for (_, mirror) in mirrors.into_iter().filter(|(enabled, _)| *enabled) {
+            for direction in Direction::ALL {
+                let state_of = |direction| {
+                    self.members.get(&direction).cloned().or_else(|| {
+                        generated
+                            .iter()
+                            .find(|member| member.direction == direction)
+                            .map(|member| member.state.clone())
+                    })
+                };
+                if state_of(direction).is_some() {
+                    continue;
+                }
+                if let Some(of) = state_of(direction.mirrored(mirror)) {
+                    generated.push(MirroredMember {
+                        direction,
+                        state: format!("{}_{}", self.name, direction),
+                        of,
+                        mirror,
+                    });
+                }
+            }
+        }
+        generated
+    }
+
+    fn to_direction_set(&self) -> DirectionSet {
+        let mut set = DirectionSet::default();
+        if let Some(degrees) = self.hysteresis_degrees {
+            set = set.with_hysteresis_degrees(degrees);
+        }
+        for (direction, state) in self.members.iter() {
+            set = set.with_member(*direction, TextLike::Ref(state));
+        }
+        for member in self.mirrored_members() {
+            set = set.with_member(member.direction, member.state);
+        }
+        set
+    }
+}
+
+/// Range of a [`BlendSerde`] which starts at the parameter value `from`.
+#[derive(Deserialize, Clone, Debug)]
+pub struct BlendRangeSerde {
+    state: String,
+    from: f32,
+    /// Parameter value at which the state plays at its normal speed.
+    reference: Option<f32>,
+}
+
+/// Picks a state from a float parameter like the speed.
+#[derive(Deserialize, Clone, Debug)]
+pub struct BlendSerde {
+    name: String,
+    ranges: Vec<BlendRangeSerde>,
+}
+
+impl BlendSerde {
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    fn to_blend_set(&self) -> BlendSet {
+        self.ranges.iter().fold(BlendSet::default(), |set, range| {
+            set.with_range(TextLike::Ref(&range.state), range.from, range.reference)
+        })
+    }
+}
+
+/// Generated state of a [`DirectionalSerde`] which shows the state `of` mirrored.
+struct MirroredMember {
+    direction: Direction,
+    state: String,
+    of: String,
+    mirror: Mirror,
 }
 
 fn true_default() -> bool {
     true
 }
 
+/// Reads a field which is optional without wrapping it in `Some(..)`.
+fn present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
 impl FramesSerde {
     pub fn name(&self) -> &str {
         self.name.as_ref()
@@ -50,24 +301,68 @@ impl FramesSerde {
             f32_to_animation_duration(raw)
         }?;
 
-        let columns = sequence_meta.columns();
-        Ok(AnimationFrames::new(
-            self.start_row,
-            self.start_column,
-            self.end_row,
-            self.end_column,
-            time_secs,
-            columns,
-            self.infinite,
-        )?)
+        let frames = match &self.images {
+            Some(images) => AnimationFrames::from_images(images.paths(), time_secs, self.infinite)?,
+            None => AnimationFrames::new(
+                self.start_row.ok_or(AnimationFrameError::NoStartRow)?,
+                self.start_column,
+                self.end_row,
+                self.end_column,
+                time_secs,
+                sequence_meta.columns(),
+                self.infinite,
+            )?,
+        };
+        let mut frames = frames.with_end_behavior(self.end_behavior);
+        frames.set_anchor(self.anchor);
+        for (offset, anchor) in self.frame_anchors.iter() {
+            frames.set_frame_anchor(*offset, Some(*anchor));
+        }
+        for tag in self.tags.iter() {
+            frames.add_tag(FrameTag::new(&tag.name, tag.from, tag.to))?;
+        }
+        for entry in self.metadata.iter() {
+            for offset in entry.from..=entry.to.unwrap_or(entry.from) {
+                for (key, value) in entry.values.iter() {
+                    frames.insert_frame_value(offset, key.as_str(), value.clone());
+                }
+            }
+        }
+        for (offset, sockets) in self.frame_sockets.iter() {
+            frames.set_frame_sockets(
+                *offset,
+                sockets
+                    .iter()
+                    .map(FrameSocketSerde::to_frame_socket)
+                    .collect(),
+            );
+        }
+        for (offset, boxes) in self.frame_boxes.iter() {
+            frames.set_frame_boxes(
+                *offset,
+                boxes.iter().map(FrameBoxSerde::to_frame_box).collect(),
+            );
+        }
+        Ok(frames)
     }
 }
 
 #[derive(Deserialize, Asset, TypePath, Clone)]
 pub struct AnimationAssets {
     init_name: Option<String>,
+    /// Path of the sprite sheet. Is required for collections inside [`AnimationCollectionsAssets`]
+    /// unless every state is made of `images`.
+    image: Option<String>,
     start_state: String,
     frames: Vec<FramesSerde>,
+    /// States which reuse the frames of another state but flipped.
+    #[serde(default)]
+    mirrored: Vec<MirroredSerde>,
+    #[serde(default)]
+    directional: Vec<DirectionalSerde>,
+    /// States which are picked from a float parameter.
+    #[serde(default)]
+    blends: Vec<BlendSerde>,
     time_secs: Option<f32>,
     #[serde(flatten)]
     general: AnimationAltlasMeta,
@@ -80,15 +375,47 @@ impl AnimationAssets {
         assets_atlas: &mut Assets<TextureAtlas>,
         default_ani_duration: PosScaleFactor,
     ) -> AnimationResult<AnimationCollection> {
-        let meta = self.general.clone().build(image, assets_atlas);
+        let meta = if self.only_images() {
+            self.general.clone().without_atlas()
+        } else {
+            self.general.clone().build(image, assets_atlas)
+        };
+        self.collection_with_atlas(meta, default_ani_duration)
+    }
+
+    /// Same as [`Self::to_animaton_collection`] but shares the texture atlas
+    /// with other collections using the same image and grid.
+    pub fn to_shared_animaton_collection(
+        &self,
+        image: Handle<Image>,
+        assets_atlas: &mut Assets<TextureAtlas>,
+        cache: &mut TextureAtlasCache,
+        default_ani_duration: PosScaleFactor,
+    ) -> AnimationResult<AnimationCollection> {
+        let meta = if self.only_images() {
+            self.general.clone().without_atlas()
+        } else {
+            self.general
+                .clone()
+                .build_shared(image, assets_atlas, cache)
+        };
+        self.collection_with_atlas(meta, default_ani_duration)
+    }
+
+    fn collection_with_atlas(
+        &self,
+        meta: AnimationAltlas,
+        default_ani_duration: PosScaleFactor,
+    ) -> AnimationResult<AnimationCollection> {
         let mut collection = AnimationCollectionBuilder::new(meta);
-        for (name, frames) in self.frames.iter().map(|to_split| {
-            (
-                TextLike::from(to_split.clone_name()).into_registered_name(),
-                to_split.to_animation_frames(self, default_ani_duration),
-            )
-        }) {
-            collection = collection.add_animation(name, frames?);
+        for (name, frames) in self.states(default_ani_duration)? {
+            collection = collection.add_animation(name, frames);
+        }
+        for (name, set) in self.direction_sets() {
+            collection = collection.add_direction_set(name, set);
+        }
+        for (name, set) in self.blend_sets() {
+            collection = collection.add_blend_set(name, set);
         }
 
         Ok(collection.build(&self.start_state))
@@ -99,25 +426,101 @@ impl AnimationAssets {
         default_ani_duration: PosScaleFactor,
     ) -> AnimationResult<AnimationSequence> {
         let mut seq = AnimationSequenceBuilder::default();
-        for (name, frames) in self.frames.iter().map(|to_split| {
-            (
-                TextLike::from(to_split.clone_name()).into_registered_name(),
-                to_split.to_animation_frames(self, default_ani_duration),
-            )
-        }) {
-            seq = seq.add_animation(name, frames?);
+        for (name, frames) in self.states(default_ani_duration)? {
+            seq = seq.add_animation(name, frames);
         }
         Ok(seq.build())
     }
 
+    /// Frames of all states including the mirrored ones.
+    fn states(
+        &self,
+        default_ani_duration: PosScaleFactor,
+    ) -> AnimationResult<Vec<(&'static str, AnimationFrames)>> {
+        let mut states = Vec::with_capacity(self.frames.len() + self.mirrored.len());
+        for frames in self.frames.iter() {
+            states.push((
+                TextLike::from(frames.clone_name()).into_registered_name(),
+                frames.to_animation_frames(self, default_ani_duration)?,
+            ));
+        }
+        for mirrored in self.mirrored.iter() {
+            let frames = states
+                .iter()
+                .find(|(name, _)| *name == mirrored.of())
+                .map(|(_, frames)| frames.mirrored(mirrored.mirror()))
+                .ok_or_else(|| NotFoundError::SingleAnimation(mirrored.of().to_string()))?;
+            states.push((
+                TextLike::Ref(mirrored.name()).into_registered_name(),
+                frames,
+            ));
+        }
+        for member in self
+            .directional
+            .iter()
+            .flat_map(DirectionalSerde::mirrored_members)
+        {
+            let frames = states
+                .iter()
+                .find(|(name, _)| *name == member.of)
+                .map(|(_, frames)| frames.mirrored(member.mirror))
+                .ok_or(NotFoundError::SingleAnimation(member.of))?;
+            states.push((TextLike::from(member.state).into_registered_name(), frames));
+        }
+        Ok(states)
+    }
+
+    /// Blend sets by their name.
+    pub fn blend_sets(&self) -> impl Iterator<Item = (&'static str, BlendSet)> + '_ {
+        self.blends.iter().map(|blend| {
+            (
+                TextLike::Ref(blend.name()).into_registered_name(),
+                blend.to_blend_set(),
+            )
+        })
+    }
+
+    /// Direction sets by their name.
+    pub fn direction_sets(&self) -> impl Iterator<Item = (&'static str, DirectionSet)> + '_ {
+        self.directional.iter().map(|directional| {
+            (
+                TextLike::Ref(directional.name()).into_registered_name(),
+                directional.to_direction_set(),
+            )
+        })
+    }
+
     pub fn start_state(&self) -> &str {
         self.start_state.as_ref()
     }
 
+    pub fn state_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.frames
+            .iter()
+            .map(FramesSerde::name)
+            .chain(self.mirrored.iter().map(MirroredSerde::name))
+            .map(|name| TextLike::Ref(name).into_registered_name())
+            .chain(
+                self.directional
+                    .iter()
+                    .flat_map(DirectionalSerde::mirrored_members)
+                    .map(|member| TextLike::from(member.state).into_registered_name()),
+            )
+    }
+
     pub fn name(&self) -> Option<&str> {
         self.init_name.as_deref()
     }
 
+    pub fn image(&self) -> Option<&str> {
+        self.image.as_deref()
+    }
+
+    /// Whether every state is made of `images`, so no sprite sheet is needed.
+    pub fn only_images(&self) -> bool {
+        self.frames.iter().all(|frames| frames.images.is_some())
+    }
+
     pub fn columns(&self) -> AnimationIndex {
         self.general.columns()
     }
@@ -127,6 +530,24 @@ impl AnimationAssets {
     }
 }
 
+/// Several collections in one file, each under its own name.
+/// The field `init_name` of a collection is ignored in favour of its name in the map.
+#[derive(Deserialize, Asset, TypePath, Clone)]
+#[serde(transparent)]
+pub struct AnimationCollectionsAssets(HashMap<String, AnimationAssets>);
+
+impl AnimationCollectionsAssets {
+    pub fn get(&self, name: &str) -> Option<&AnimationAssets> {
+        self.0.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &AnimationAssets)> {
+        self.0
+            .iter()
+            .map(|(name, animations)| (name.as_str(), animations))
+    }
+}
+
 pub fn f32_to_animation_duration(time: f32) -> Result<AnimationDuration, NegativeAnimationTime> {
     if time < 0. {
         Err(NegativeAnimationTime(time))
@@ -134,3 +555,115 @@ pub fn f32_to_animation_duration(time: f32) -> Result<AnimationDuration, Negativ
         Ok(bevy::utils::Duration::from_secs_f32(time))
     }
 }
+
+#[cfg(test)]
+mod testing {
+    use super::*;
+    use crate::animation_error::AnimationError;
+
+    /// Frames of the first state inside a collection of 4 x 4 cells.
+    fn frames_of(state: &str) -> AnimationFrames {
+        let animations: AnimationAssets = ron::from_str(&format!(
+            r#"{{
+                start_state: "Idle",
+                frames: [{state}],
+                rows: 4,
+                columns: 4,
+                cell_size: (16, 16),
+            }}"#
+        ))
+        .unwrap();
+        let (_, frames) = animations
+            .states(PosScaleFactor::default())
+            .unwrap()
+            .remove(0);
+        frames
+    }
+
+    #[test]
+    fn applies_metadata_ranges_with_later_entries_winning() {
+        let frames = frames_of(
+            r#"(
+                name: "Idle",
+                start_row: 0,
+                metadata: [
+                    (from: 0, to: Some(2), values: {"invulnerable": true, "surface": "stone"}),
+                    (from: 1, values: {"surface": "grass"}),
+                ],
+            )"#,
+        );
+        let value = |offset: usize, key: &str| {
+            frames
+                .metadata_at(frames.start() + offset)
+                .and_then(|values| values.get(key))
+                .cloned()
+        };
+        for offset in 0..=2 {
+            assert_eq!(Some(FrameValue::Bool(true)), value(offset, "invulnerable"));
+        }
+        let surfaces: Vec<_> = (0..=2).map(|offset| value(offset, "surface")).collect();
+        assert_eq!(
+            vec![
+                Some(FrameValue::from("stone")),
+                Some(FrameValue::from("grass")),
+                Some(FrameValue::from("stone")),
+            ],
+            surfaces
+        );
+        assert!(frames.metadata_at(frames.start() + 3).is_none());
+    }
+
+    #[test]
+    fn expands_image_pattern_with_padded_numbers() {
+        let frames = frames_of(
+            r#"(
+                name: "Idle",
+                images: Some(Pattern(path: "boss/idle_{}.png", first: 8, last: 10, digits: 3)),
+            )"#,
+        );
+        let images: Vec<_> = (0..frames.frame_count())
+            .map(|offset| frames.image_at(frames.start() + offset))
+            .collect();
+        assert_eq!(
+            vec![
+                Some("boss/idle_008.png"),
+                Some("boss/idle_009.png"),
+                Some("boss/idle_010.png"),
+            ],
+            images
+        );
+        assert_eq!(None, frames.image_at(frames.start() + 3));
+    }
+
+    #[test]
+    fn expands_image_pattern_without_padding_by_default() {
+        let frames = frames_of(
+            r#"(
+                name: "Idle",
+                images: Some(Pattern(path: "idle_{}.png", first: 9, last: 10)),
+            )"#,
+        );
+        assert_eq!(Some("idle_9.png"), frames.image_at(frames.start()));
+        assert_eq!(Some("idle_10.png"), frames.image_at(frames.start() + 1));
+    }
+
+    #[test]
+    fn requires_start_row_without_images() {
+        let animations: AnimationAssets = ron::from_str(
+            r#"{
+                start_state: "Idle",
+                frames: [(name: "Idle")],
+                rows: 4,
+                columns: 4,
+                cell_size: (16, 16),
+            }"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            animations.states(PosScaleFactor::default()),
+            Err(AnimationError::InvalidFrames(
+                AnimationFrameError::NoStartRow
+            ))
+        ));
+    }
+}
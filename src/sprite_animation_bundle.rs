@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 
-use crate::{animation_comp::AnimationComp, animation_time_factor::AnimationTimeScale};
+use crate::{
+    animation_collection::AnimationCollection, animation_comp::AnimationComp,
+    animation_time_factor::AnimationTimeScale, collection_look_up::start_of_asset,
+    types::KeyLookUpResult,
+};
 
 #[derive(Bundle)]
 pub struct SpriteAnimationBundle {
@@ -8,3 +12,40 @@ pub struct SpriteAnimationBundle {
     pub frames: AnimationComp,
     pub time_scale: AnimationTimeScale,
 }
+
+impl SpriteAnimationBundle {
+    /// Creates a bundle which starts at the start state of a loaded collection asset.
+    /// # Errors
+    /// Returns an error if the asset is not loaded yet.
+    pub fn from_asset(
+        collection: Handle<AnimationCollection>,
+        assets: &Assets<AnimationCollection>,
+    ) -> KeyLookUpResult<Self> {
+        let (frames, animations, start_index) = start_of_asset(collection, assets)?;
+        Ok(Self::new(frames, animations, start_index))
+    }
+
+    pub(crate) fn new(
+        mut frames: AnimationComp,
+        animations: &AnimationCollection,
+        start_index: usize,
+    ) -> Self {
+        let mut sprite = TextureAtlasSprite::new(start_index);
+        if let Ok(start_frames) = animations.get_frames_under(frames.current_state()) {
+            if let Some(mirror) = start_frames.mirror() {
+                mirror.apply(&mut sprite);
+            }
+            frames.apply_anchor(&mut sprite, &start_frames);
+        }
+        let sprite_sheet = SpriteSheetBundle {
+            texture_atlas: animations.atlas(),
+            sprite,
+            ..default()
+        };
+        Self {
+            sprite_sheet,
+            time_scale: Default::default(),
+            frames,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation_collection::AnimationCollection, animation_comp::AnimationComp,
+    animation_time_factor::AnimationTimeScale, collection_look_up::start_of_asset,
+    frame_target::SpriteRectFrame, types::KeyLookUpResult,
+};
+
+/// Same as [`SpriteAnimationBundle`](crate::prelude::SpriteAnimationBundle)
+/// for a plain [`Sprite`] showing cells of `image` via its rect.
+/// `image` is expected to be the image the grid of the collection was made for.
+#[derive(Bundle)]
+pub struct SpriteRectAnimationBundle {
+    pub sprite: SpriteBundle,
+    pub frame: SpriteRectFrame,
+    pub frames: AnimationComp,
+    pub time_scale: AnimationTimeScale,
+}
+
+impl SpriteRectAnimationBundle {
+    /// Same as [`SpriteAnimationBundle::from_asset`](crate::prelude::SpriteAnimationBundle::from_asset).
+    pub fn from_asset(
+        collection: Handle<AnimationCollection>,
+        image: Handle<Image>,
+        assets: &Assets<AnimationCollection>,
+    ) -> KeyLookUpResult<Self> {
+        let (frames, animations, start_index) = start_of_asset(collection, assets)?;
+        Ok(Self::new(frames, animations, start_index, image))
+    }
+
+    pub(crate) fn new(
+        mut frames: AnimationComp,
+        animations: &AnimationCollection,
+        start_index: usize,
+        image: Handle<Image>,
+    ) -> Self {
+        let mut frame = SpriteRectFrame::new(start_index);
+        if let Ok(start_frames) = animations.get_frames_under(frames.current_state()) {
+            if let Some(mirror) = start_frames.mirror() {
+                mirror.apply(&mut frame);
+            }
+            frames.apply_anchor(&mut frame, &start_frames);
+        }
+        let mut sprite = Sprite::default();
+        frame.write_to(&mut sprite, animations.grid());
+        Self {
+            sprite: SpriteBundle {
+                sprite,
+                texture: image,
+                ..default()
+            },
+            frame,
+            time_scale: Default::default(),
+            frames,
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        animation_respo_resource::AllAnimationResource,
+        listen_animation_end::ListenAnimationEnd,
+        prelude::AnimationCollectionBuilder,
+        test_app::{self, KEY},
+        AnimationEnded, AnimationPrecentProgress, PercentScaleFactor,
+    };
+
+    #[test]
+    fn writes_rect_of_current_frame() {
+        let mut app = test_app::app();
+        test_app::count_changes::<Sprite>(&mut app);
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_row_ani("Walk", 0, test_app::ROW_TIME, true)
+            .add_row_ani("Once", 1, test_app::ROW_TIME, false)
+            .build("Walk");
+        test_app::add_collection(&mut app, collection);
+        let bundle = app
+            .world
+            .resource::<AllAnimationResource>()
+            .create_sprite_rect_comp(KEY, Handle::default())
+            .unwrap();
+        let sprite = app
+            .world
+            .spawn((
+                bundle,
+                AnimationPrecentProgress::default(),
+                ListenAnimationEnd,
+            ))
+            .id();
+        let rect = |app: &App| app.world.get::<Sprite>(sprite).unwrap().rect.unwrap();
+        let progress = |app: &App| {
+            app.world
+                .get::<AnimationPrecentProgress>(sprite)
+                .unwrap()
+                .value()
+        };
+        let changes = test_app::changes::<Sprite>;
+
+        test_app::tick(&mut app, Duration::ZERO);
+        assert_eq!(Rect::new(0., 0., 16., 16.), rect(&app));
+        let written = changes(&app);
+        test_app::tick(&mut app, Duration::from_millis(100));
+        assert_eq!(written, changes(&app));
+        test_app::tick(&mut app, Duration::from_millis(150));
+        assert_eq!(Rect::new(16., 0., 32., 16.), rect(&app));
+        assert_eq!(PercentScaleFactor::new(1. / 3.).unwrap(), progress(&app));
+        assert_eq!(written + 1, changes(&app));
+
+        test_app::switch_to(&mut app, sprite, "Once");
+        assert_eq!(Rect::new(0., 16., 16., 32.), rect(&app));
+        assert_eq!(PercentScaleFactor::zero(), progress(&app));
+        test_app::drain_events::<AnimationEnded>(&mut app);
+
+        test_app::tick(&mut app, Duration::from_millis(750));
+        assert_eq!(Rect::new(48., 16., 64., 32.), rect(&app));
+        assert!(progress(&app).is_complete());
+        test_app::tick(&mut app, Duration::from_millis(250));
+        let ended = test_app::drain_events::<AnimationEnded>(&mut app);
+        assert_eq!(1, ended.len());
+        assert_eq!("Once", ended[0].state.as_ref());
+    }
+}
@@ -2,189 +2,267 @@ use bevy::{ecs::query::Has, prelude::*};
 
 #[cfg(feature = "assets")]
 pub mod asset_handling;
+pub mod collection_changes;
+pub mod frame_boxes;
+pub mod frame_metadata;
+pub mod frame_sockets;
+pub mod frame_space;
+pub mod frame_tags;
+pub mod image_frames;
+pub mod sprite_rects;
 
 use crate::{
-    animation_comp::{get_animation_seq, new_reapting_time, AnimationComp},
-    animation_frames::AnimationFrames,
+    animation_comp::{new_reapting_time, AnimationComp},
+    animation_frames::{AnimationFrames, Mirror},
+    animation_lifecycle::{AnimationLifecycle, LifecycleKind, ListenLifecycle},
+    animation_respo_resource::AllAnimationResource,
     animation_time_factor::AnimationTimeScale,
+    collection_look_up::AnimationCollections,
+    end_behavior::EndBehavior,
+    frame_target::FrameTarget,
     listen_animation_end::ListenAnimationEnd,
-    prelude::AllAnimationResource,
-    types::AnimationResult,
+    pause_animation::{AnimationPaused, AnimationResumed, PauseAnimation},
+    types::{AnimationIndex, AnimationResult},
     utils, AnimationEnded, AnimationPrecentProgress, PercentScaleFactor,
 };
 
 #[allow(clippy::type_complexity)]
-pub fn animate(
-    query: Query<(
-        Entity,
-        &mut AnimationComp,
-        &mut TextureAtlasSprite,
-        &AnimationTimeScale,
-        Option<&mut AnimationPrecentProgress>,
-        Has<ListenAnimationEnd>,
-    )>,
+pub fn animate<T: FrameTarget>(
+    query: Query<
+        (
+            Entity,
+            &mut AnimationComp,
+            &mut T,
+            &AnimationTimeScale,
+            Option<&mut AnimationPrecentProgress>,
+            Has<ListenAnimationEnd>,
+            Option<&ListenLifecycle>,
+        ),
+        Without<PauseAnimation>,
+    >,
     time: Res<Time<Virtual>>,
-    repos: Res<AllAnimationResource>,
+    repos: AnimationCollections,
     on_animation_finish: EventWriter<AnimationEnded>,
+    on_lifecycle: EventWriter<AnimationLifecycle>,
 ) {
     if time.is_paused() {
         return;
     }
 
-    let result = try_apply_update(query, &time, &repos, on_animation_finish);
-    utils::log_if_error(result, "Updating animation frame over time failed.");
+    apply_update(query, &time, &repos, on_animation_finish, on_lifecycle);
 
-    fn try_apply_update(
-        mut query: Query<(
-            Entity,
-            &mut AnimationComp,
-            &mut TextureAtlasSprite,
-            &AnimationTimeScale,
-            Option<&mut AnimationPrecentProgress>,
-            Has<ListenAnimationEnd>,
-        )>,
+    fn apply_update<T: FrameTarget>(
+        mut query: Query<
+            (
+                Entity,
+                &mut AnimationComp,
+                &mut T,
+                &AnimationTimeScale,
+                Option<&mut AnimationPrecentProgress>,
+                Has<ListenAnimationEnd>,
+                Option<&ListenLifecycle>,
+            ),
+            Without<PauseAnimation>,
+        >,
         time: &Time<Virtual>,
-        repos: &AllAnimationResource,
+        repos: &AnimationCollections,
         mut on_animation_finish: EventWriter<AnimationEnded>,
-    ) -> AnimationResult {
+        mut on_lifecycle: EventWriter<AnimationLifecycle>,
+    ) {
         let mut animations_finished: Vec<AnimationEnded> = Vec::new();
-        for (who, mut animator, mut current_sprite, time_scale, mut progress, listen_end) in
-            query.iter_mut()
+        let mut lifecycle: Vec<AnimationLifecycle> = Vec::new();
+        for (
+            who,
+            mut animator,
+            mut target,
+            time_scale,
+            mut progress,
+            listen_end,
+            listen_lifecycle,
+        ) in query.iter_mut()
         {
-            let current_animation = animator.get_current_seq(repos)?;
-            match progress.as_deref_mut() {
+            // A missing collection or state only stops this entity, not all others.
+            let looked_up = animator.get_collection(repos).and_then(|collection| {
+                Ok((
+                    collection,
+                    collection.get_frames_under(animator.current_state())?,
+                ))
+            });
+            let (collection, current_animation) = match looked_up {
+                Ok(looked_up) => looked_up,
+                Err(error) => {
+                    utils::log_if_error(Err(error), "Updating animation frame over time failed.");
+                    continue;
+                }
+            };
+            let time_scale = &animator.combined_scale(collection, time_scale);
+            let (previous_index, previous_loops) = (target.index(), animator.loop_count);
+            let shown_before = shown_frame(&*target);
+            let to_adjust = target.bypass_change_detection();
+            if let Some(mirror) = current_animation.mirror() {
+                mirror.apply(to_adjust);
+            }
+            let times_ended = match progress.as_deref_mut() {
                 Some(AnimationPrecentProgress {
                     manual: true,
                     progress,
                 }) => {
                     let new_index = current_animation.index_from_precent(*progress);
-                    current_sprite.index = new_index;
+                    *to_adjust.index_mut() = new_index;
+                    0
                 }
                 Some(AnimationPrecentProgress {
                     manual: false,
                     progress,
                 }) => {
-                    update_over_time(
+                    let times_ended = update_over_time(
                         who,
                         &mut animator,
-                        &mut current_sprite,
+                        to_adjust,
                         time,
                         &current_animation,
                         time_scale,
                         &mut animations_finished,
                         listen_end,
                     );
-                    *progress = current_animation.precent(current_sprite.index);
+                    *progress = current_animation.precent(to_adjust.index());
+                    times_ended
                 }
                 None => update_over_time(
                     who,
                     &mut animator,
-                    &mut current_sprite,
+                    to_adjust,
                     time,
                     &current_animation,
                     time_scale,
                     &mut animations_finished,
                     listen_end,
                 ),
+            };
+
+            animator.apply_anchor(to_adjust, &current_animation);
+            if shown_frame(&*target) != shown_before {
+                target.set_changed();
+            }
+
+            if listen_lifecycle.is_none() {
+                continue;
+            }
+            let frame = target.index();
+            if frame != previous_index {
+                ListenLifecycle::report(
+                    listen_lifecycle,
+                    &mut lifecycle,
+                    who,
+                    LifecycleKind::FrameChanged {
+                        from: previous_index,
+                    },
+                    &animator,
+                    frame,
+                );
+            }
+            let end_kind = if current_animation.is_infinite() {
+                LifecycleKind::LoopCompleted
+            } else {
+                LifecycleKind::Finished
+            };
+            if listen_lifecycle.is_some_and(|listen| listen.0.allows(&end_kind)) {
+                lifecycle.extend((1..=times_ended as u32).map(|loops| AnimationLifecycle {
+                    loop_count: previous_loops + loops,
+                    ..AnimationLifecycle::new(who, end_kind.clone(), &animator, frame)
+                }));
             }
         }
 
         if !animations_finished.is_empty() {
             on_animation_finish.send_batch(animations_finished);
         }
-        return Ok(());
+        if !lifecycle.is_empty() {
+            on_lifecycle.send_batch(lifecycle);
+        }
 
+        /// Returns how often the last frame ended.
         #[allow(clippy::too_many_arguments)]
-        fn update_over_time(
+        fn update_over_time<T: FrameTarget>(
             who: Entity,
             animator: &mut AnimationComp,
-            current_sprite: &mut TextureAtlasSprite,
+            target: &mut T,
             time: &Time<Virtual>,
             current_animation: &AnimationFrames,
             time_scale: &AnimationTimeScale,
             animations_finished: &mut Vec<AnimationEnded>,
             listen_end: bool,
-        ) {
-            if animator.has_reached_end_without_repeat {
-                return;
-            }
-
+        ) -> usize {
             let scaled_time = time_scale.scale_duration(time.delta());
+            let times_ended = animator.advance(target.index_mut(), current_animation, scaled_time);
 
-            if animator
-                .duration_for_animation
-                .tick(scaled_time)
-                .just_finished()
-            {
-                let last_frame_has_ended = current_animation.end() == current_sprite.index;
-                let report_end_of_last_frame = listen_end && last_frame_has_ended;
-                let has_reached_end_without_repeat =
-                    last_frame_has_ended && !current_animation.is_infinite();
-
-                if report_end_of_last_frame {
-                    animations_finished.push(AnimationEnded::new_complete(
-                        who,
-                        animator.current_state.clone(),
-                    ));
-                }
-
-                if has_reached_end_without_repeat {
-                    animator.has_reached_end_without_repeat = true;
-                } else {
-                    let next = current_sprite.index + 1;
-                    animator.duration_for_animation =
-                        crate::animation_comp::new_reapting_time(current_animation.time());
-
-                    current_sprite.index = if next > current_animation.end() {
-                        current_animation.start()
-                    } else {
-                        next
-                    };
-                }
+            if listen_end {
+                animations_finished
+                    .extend((0..times_ended).map(|_| {
+                        AnimationEnded::new_complete(who, animator.current_state.clone())
+                    }));
             }
+            times_ended
         }
     }
 }
 
 #[allow(clippy::type_complexity)]
-pub fn apply_pending_states(
+pub fn apply_pending_states<T: FrameTarget>(
     mut query: Query<(
         Entity,
         &mut AnimationComp,
-        &mut TextureAtlasSprite,
+        &mut T,
         Option<&mut AnimationPrecentProgress>,
         Has<ListenAnimationEnd>,
+        Option<&ListenLifecycle>,
     )>,
-    repos: Res<AllAnimationResource>,
+    repos: AnimationCollections,
     mut on_animation_switch: EventWriter<AnimationEnded>,
+    mut on_lifecycle: EventWriter<AnimationLifecycle>,
 ) {
-    for (who, mut animmator, mut texture_sprite, mut progress, listen_ani_end) in query.iter_mut() {
+    let mut lifecycle: Vec<AnimationLifecycle> = Vec::new();
+    for (who, mut animmator, mut target, mut progress, listen_ani_end, listen_lifecycle) in
+        query.iter_mut()
+    {
         let mut animations_finished: Vec<AnimationEnded> = Vec::new();
+        // Other states may show other images under the same index.
+        let switches = animmator.next_state.is_some();
+        let shown_before = shown_frame(&*target);
         utils::log_if_error(
             try_apply_state_change(
                 who,
                 &mut animmator,
-                &mut texture_sprite,
+                target.bypass_change_detection(),
                 progress.as_deref_mut(),
-                listen_ani_end,
+                (listen_ani_end, listen_lifecycle),
                 &repos,
                 &mut animations_finished,
+                &mut lifecycle,
             ),
             "Applying state change for animation failed.",
         );
+        if switches || shown_frame(&*target) != shown_before {
+            target.set_changed();
+        }
         if !animations_finished.is_empty() {
             on_animation_switch.send_batch(animations_finished)
         }
     }
+    if !lifecycle.is_empty() {
+        on_lifecycle.send_batch(lifecycle);
+    }
 
-    fn try_apply_state_change(
+    #[allow(clippy::too_many_arguments)]
+    fn try_apply_state_change<T: FrameTarget>(
         who: Entity,
         animator: &mut AnimationComp,
-        to_adjust: &mut TextureAtlasSprite,
+        to_adjust: &mut T,
         progress: Option<&mut AnimationPrecentProgress>,
-        listen_animation_end: bool,
-        respo: &AllAnimationResource,
+        (listen_animation_end, listen_lifecycle): (bool, Option<&ListenLifecycle>),
+        respo: &AnimationCollections,
         on_change: &mut Vec<AnimationEnded>,
+        lifecycle: &mut Vec<AnimationLifecycle>,
     ) -> AnimationResult {
         if let Some(new) = animator.next_state.take() {
             if let Some(to_reset) = progress {
@@ -193,45 +271,327 @@ pub fn apply_pending_states(
             if listen_animation_end {
                 let current_animation = animator.get_current_seq(respo)?;
                 let state = animator.current_state.clone();
-                let progress = current_animation.precent(to_adjust.index);
+                let progress = current_animation.precent(to_adjust.index());
                 on_change.push(AnimationEnded {
                     who,
                     state,
                     progress,
                 });
             }
+            if !std::mem::take(&mut animator.ended_before_switch) {
+                ListenLifecycle::report(
+                    listen_lifecycle,
+                    lifecycle,
+                    who,
+                    LifecycleKind::Interrupted { next: new.clone() },
+                    animator,
+                    to_adjust.index(),
+                );
+            }
 
-            let (new_time, start) = {
-                let (new_key, new_animation) = get_animation_seq(respo, &animator.sequence, &new)?;
-                animator.current_state = new_key.into();
-                (new_animation.time_per_frame(), new_animation.start())
-            };
-            animator.duration_for_animation = new_reapting_time(new_time);
-            to_adjust.index = start;
+            let previous = animator.get_current_seq(respo).ok();
+            let was_mirrored = previous
+                .as_ref()
+                .is_some_and(|previous| previous.mirror().is_some());
+            let phase = previous
+                .filter(|_| animator.keep_phase)
+                .map(|previous| animator.phase(to_adjust.index(), &previous));
+            animator.keep_phase = false;
+
+            let (new_key, new_animation) =
+                animator.get_collection(respo)?.key_and_frames_under(&new)?;
+            animator.current_state = new_key.into();
+            // Flips set by hand stay untouched as long as no mirrored state is involved.
+            if was_mirrored || new_animation.mirror().is_some() {
+                new_animation.mirror().unwrap_or_default().apply(to_adjust);
+            }
+            animator.duration_for_animation = new_reapting_time(new_animation.time_per_frame());
+            animator.has_reached_end_without_repeat = false;
+            animator.loop_count = 0;
+            *to_adjust.index_mut() = new_animation.start();
+            if let Some(phase) = phase {
+                let time = new_animation.total_time().mul_f64(phase);
+                animator.seek(to_adjust.index_mut(), &new_animation, time);
+            }
+            animator.apply_anchor(to_adjust, &new_animation);
+            let start = to_adjust.index();
+            ListenLifecycle::report(
+                listen_lifecycle,
+                lifecycle,
+                who,
+                LifecycleKind::Started,
+                animator,
+                start,
+            );
         }
         Ok(())
     }
 }
-pub fn do_pending_resets(
-    mut query: Query<(&mut AnimationComp, &mut TextureAtlasSprite)>,
-    repos: Res<AllAnimationResource>,
+
+pub fn report_started_animations<T: FrameTarget>(
+    query: Query<(Entity, &AnimationComp, &T, &ListenLifecycle), Added<AnimationComp>>,
+    mut on_lifecycle: EventWriter<AnimationLifecycle>,
+) {
+    let mut lifecycle: Vec<AnimationLifecycle> = Vec::new();
+    for (who, animator, target, listen) in query.iter() {
+        ListenLifecycle::report(
+            Some(listen),
+            &mut lifecycle,
+            who,
+            LifecycleKind::Started,
+            animator,
+            target.index(),
+        );
+    }
+    if !lifecycle.is_empty() {
+        on_lifecycle.send_batch(lifecycle);
+    }
+}
+
+/// What a frame target shows. Running every frame, [`animate`] and [`apply_pending_states`]
+/// only mark a target as changed if this differs, so `Changed<T>` filters stay meaningful.
+fn shown_frame<T: FrameTarget>(target: &T) -> (AnimationIndex, Mirror, Option<Vec2>) {
+    (
+        target.index(),
+        target.flip(),
+        target.anchor().map(|anchor| anchor.as_vec()),
+    )
+}
+
+pub fn do_pending_resets<T: FrameTarget>(
+    mut query: Query<(Entity, &mut AnimationComp, &mut T, Option<&ListenLifecycle>)>,
+    repos: AnimationCollections,
+    mut on_lifecycle: EventWriter<AnimationLifecycle>,
 ) {
-    for (mut animator, mut texture_sprite) in query.iter_mut() {
-        let result = apply_state_reset(&mut animator, &mut texture_sprite, &repos);
+    let mut lifecycle: Vec<AnimationLifecycle> = Vec::new();
+    for (who, mut animator, mut target, listen_lifecycle) in query.iter_mut() {
+        if !animator.reset_state {
+            continue;
+        }
+        let result = apply_state_reset(&mut animator, &mut *target, &repos);
+        if result.is_ok() {
+            ListenLifecycle::report(
+                listen_lifecycle,
+                &mut lifecycle,
+                who,
+                LifecycleKind::Reset,
+                &animator,
+                target.index(),
+            );
+        }
         utils::log_if_error(result, "Applying state reset for animation failed.");
     }
+    if !lifecycle.is_empty() {
+        on_lifecycle.send_batch(lifecycle);
+    }
 
-    fn apply_state_reset(
+    fn apply_state_reset<T: FrameTarget>(
         animator: &mut AnimationComp,
-        texture_sprite: &mut TextureAtlasSprite,
-        repos: &AllAnimationResource,
+        target: &mut T,
+        repos: &AnimationCollections,
     ) -> AnimationResult<()> {
         if animator.reset_state {
             animator.reset_state = false;
+            animator.loop_count = 0;
             let current_animation = animator.get_current_seq(repos)?;
-            texture_sprite.index = current_animation.start();
+            *target.index_mut() = current_animation.start();
             animator.duration_for_animation.reset();
         }
         Ok(())
     }
 }
+
+pub fn apply_end_behaviors<T: FrameTarget>(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut AnimationComp, &mut T, Option<&mut Visibility>)>,
+    repos: AnimationCollections,
+) {
+    for (who, mut animator, mut target, visibility) in query.iter_mut() {
+        if !animator.pending_end {
+            continue;
+        }
+        animator.pending_end = false;
+        let result = animator.end_behavior(&repos).and_then(|end_behavior| {
+            match end_behavior {
+                EndBehavior::Hold => (),
+                EndBehavior::Rewind => {
+                    *target.index_mut() = animator.start_index(&repos)?;
+                    animator.duration_for_animation.reset();
+                }
+                EndBehavior::Hide => {
+                    if let Some(mut visibility) = visibility {
+                        *visibility = Visibility::Hidden;
+                    }
+                }
+                EndBehavior::Despawn => commands.entity(who).despawn_recursive(),
+                EndBehavior::RemoveAnimation => {
+                    commands.entity(who).remove::<AnimationComp>();
+                }
+            }
+            Ok(())
+        });
+        utils::log_if_error(result, "Applying end behavior of animation failed.");
+    }
+}
+
+pub fn prune_atlas_cache(mut repos: ResMut<AllAnimationResource>) {
+    repos.bypass_change_detection().atlas_cache_mut().prune();
+}
+
+pub fn report_pause_changes(
+    paused: Query<Entity, Added<PauseAnimation>>,
+    mut resumed: RemovedComponents<PauseAnimation>,
+    still_exists: Query<(), With<AnimationComp>>,
+    mut on_pause: EventWriter<AnimationPaused>,
+    mut on_resume: EventWriter<AnimationResumed>,
+) {
+    on_pause.send_batch(paused.iter().map(|who| AnimationPaused { who }));
+    on_resume.send_batch(
+        resumed
+            .read()
+            .filter(|who| still_exists.contains(*who))
+            .map(|who| AnimationResumed { who }),
+    );
+}
+
+#[cfg(test)]
+mod testing {
+    use std::time::Duration;
+
+    use bevy::sprite::Anchor;
+
+    use super::*;
+    use crate::{
+        prelude::AnimationCollectionBuilder,
+        test_app::{self, index, switch_to, KEY},
+        Mirror,
+    };
+
+    /// Sprite playing a state of 4 frames without repeat which ends after one second.
+    fn app_with(end_behavior: EndBehavior) -> (App, Entity) {
+        let mut app = test_app::app();
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_row_ani("Once", 0, test_app::ROW_TIME, false)
+            .build("Once");
+        test_app::add_collection(&mut app, collection);
+        let mut bundle = app
+            .world
+            .resource::<AllAnimationResource>()
+            .create_sprite_comp(KEY)
+            .unwrap();
+        bundle.frames = bundle.frames.with_end_behavior(end_behavior);
+        let sprite = app.world.spawn(bundle).id();
+        test_app::tick(&mut app, Duration::ZERO);
+        test_app::tick(&mut app, Duration::from_millis(1_100));
+        (app, sprite)
+    }
+
+    #[test]
+    fn holds_or_rewinds_after_end() {
+        let (app, sprite) = app_with(EndBehavior::Hold);
+        assert_eq!(3, index(&app, sprite));
+
+        let (mut app, sprite) = app_with(EndBehavior::Rewind);
+        assert_eq!(0, index(&app, sprite));
+        test_app::tick(&mut app, Duration::from_secs(1));
+        assert_eq!(0, index(&app, sprite));
+        assert!(
+            app.world
+                .get::<AnimationComp>(sprite)
+                .unwrap()
+                .has_reached_end_without_repeat
+        );
+    }
+
+    #[test]
+    fn hides_after_end() {
+        let (app, sprite) = app_with(EndBehavior::Hide);
+        assert_eq!(
+            Visibility::Hidden,
+            *app.world.get::<Visibility>(sprite).unwrap()
+        );
+        assert_eq!(3, index(&app, sprite));
+    }
+
+    #[test]
+    fn despawns_or_removes_animation_after_end() {
+        let (app, sprite) = app_with(EndBehavior::Despawn);
+        assert!(app.world.get_entity(sprite).is_none());
+
+        let (app, sprite) = app_with(EndBehavior::RemoveAnimation);
+        assert!(app.world.get::<AnimationComp>(sprite).is_none());
+        assert_eq!(3, index(&app, sprite));
+    }
+
+    #[test]
+    fn flips_only_while_mirrored_state_is_active() {
+        let mut app = test_app::app();
+        let time = test_app::ROW_TIME;
+        let left = AnimationFrames::from_row(0, time, test_app::COLUMNS, true).unwrap();
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_animation("Right", left.mirrored(Mirror { x: true, y: false }))
+            .add_animation("Upside", left.mirrored(Mirror { x: false, y: true }))
+            .add_animation("Left", left)
+            .add_row_ani("Up", 1, time, true)
+            .build("Left");
+        test_app::add_collection(&mut app, collection);
+        let sprite = test_app::spawn_sprite(&mut app);
+        test_app::tick(&mut app, Duration::ZERO);
+        let flips = |app: &App| {
+            let sprite = app.world.get::<TextureAtlasSprite>(sprite).unwrap();
+            (sprite.flip_x, sprite.flip_y)
+        };
+        assert_eq!((false, false), flips(&app));
+
+        switch_to(&mut app, sprite, "Right");
+        assert_eq!((true, false), flips(&app));
+        switch_to(&mut app, sprite, "Upside");
+        assert_eq!((false, true), flips(&app));
+        switch_to(&mut app, sprite, "Up");
+        assert_eq!((false, false), flips(&app));
+
+        // Flips set by hand stay untouched as long as no mirrored state is involved.
+        app.world
+            .get_mut::<TextureAtlasSprite>(sprite)
+            .unwrap()
+            .flip_y = true;
+        switch_to(&mut app, sprite, "Left");
+        assert_eq!((false, true), flips(&app));
+    }
+
+    #[test]
+    fn restores_base_anchor_after_anchored_state() {
+        let mut app = test_app::app();
+        let time = test_app::ROW_TIME;
+        let mut anchored = AnimationFrames::from_row(1, time, test_app::COLUMNS, true)
+            .unwrap()
+            .with_anchor(Vec2::new(0., -0.25));
+        anchored.set_frame_anchor(1, Some(Vec2::new(0.1, -0.25)));
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_row_ani("Plain", 0, time, true)
+            .add_animation("Anchored", anchored)
+            .build("Plain");
+        test_app::add_collection(&mut app, collection);
+        let sprite = test_app::spawn_sprite(&mut app);
+        app.world
+            .get_mut::<TextureAtlasSprite>(sprite)
+            .unwrap()
+            .anchor = Anchor::BottomLeft;
+        test_app::tick(&mut app, Duration::ZERO);
+        let anchor = |app: &App| {
+            let sprite = app.world.get::<TextureAtlasSprite>(sprite).unwrap();
+            sprite.anchor.as_vec()
+        };
+        assert_eq!(Anchor::BottomLeft.as_vec(), anchor(&app));
+
+        switch_to(&mut app, sprite, "Anchored");
+        assert_eq!(Vec2::new(0., -0.25), anchor(&app));
+        test_app::tick(&mut app, Duration::from_millis(250));
+        assert_eq!(Vec2::new(0.1, -0.25), anchor(&app));
+        test_app::tick(&mut app, Duration::from_millis(250));
+        assert_eq!(Vec2::new(0., -0.25), anchor(&app));
+
+        switch_to(&mut app, sprite, "Plain");
+        assert_eq!(Anchor::BottomLeft.as_vec(), anchor(&app));
+    }
+}
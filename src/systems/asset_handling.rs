@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 
-use crate::{animation_respo_resource::AllAnimationResource, save_load::AnimationAssets};
+use crate::{
+    animation_respo_resource::AllAnimationResource,
+    save_load::{AnimationAssets, AnimationCollectionsAssets},
+    systems::collection_changes::take_collection_changes,
+};
 
 #[allow(clippy::single_match)]
 pub fn reload_animation_assets(
@@ -23,6 +27,33 @@ pub fn reload_animation_assets(
     }
 }
 
+pub fn reload_multi_animation_assets(
+    assets_antimation: Res<Assets<AnimationCollectionsAssets>>,
+    asset_server: Res<AssetServer>,
+    mut assets_atlas: ResMut<Assets<TextureAtlas>>,
+    mut asset_event: EventReader<AssetEvent<AnimationCollectionsAssets>>,
+    mut repository: ResMut<AllAnimationResource>,
+) {
+    for event in asset_event.read() {
+        if let AssetEvent::Modified { id } = event {
+            if let Err(error) = repository.replace_from_multi_assets(
+                id,
+                &assets_antimation,
+                &asset_server,
+                &mut assets_atlas,
+            ) {
+                error!(
+                    "Changes from animation assets not applied, due to error.\nDetails: {}",
+                    error
+                )
+            }
+        }
+    }
+}
+
 pub fn regisiter_systems(app: &mut App) {
-    app.add_systems(Update, reload_animation_assets);
+    app.add_systems(
+        Update,
+        (reload_animation_assets, reload_multi_animation_assets).before(take_collection_changes),
+    );
 }
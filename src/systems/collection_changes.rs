@@ -0,0 +1,306 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation_collection::AnimationCollection,
+    animation_collection_changed::{
+        AnimationCollectionChanged, AnimationFellBackToStart, StateChange,
+    },
+    animation_comp::AnimationComp,
+    animation_lifecycle::{AnimationLifecycle, LifecycleKind, ListenLifecycle},
+    animation_respo_resource::AllAnimationResource,
+    collection_look_up::CollectionId,
+    frame_target::FrameTarget,
+    utils, AnimationPrecentProgress,
+};
+
+/// Changes of collections in the current update, applied to every [`FrameTarget`]
+/// before they are sent as [`AnimationCollectionChanged`].
+#[derive(Resource, Default)]
+pub struct PendingCollectionChanges(Vec<(CollectionId, Vec<StateChange>)>);
+
+pub fn take_collection_changes(
+    mut repos: ResMut<AllAnimationResource>,
+    mut assets: ResMut<Assets<AnimationCollection>>,
+    mut asset_events: EventReader<AssetEvent<AnimationCollection>>,
+    mut pending: ResMut<PendingCollectionChanges>,
+) {
+    if repos.has_pending_changes() {
+        pending.0.extend(repos.take_changes());
+    }
+    for event in asset_events.read() {
+        if let AssetEvent::Modified { id } = event {
+            let has_changes = assets
+                .get(*id)
+                .is_some_and(AnimationCollection::has_pending_changes);
+            if has_changes {
+                let collection = assets.get_mut(*id).unwrap();
+                pending
+                    .0
+                    .push((CollectionId::Asset(*id), collection.take_changes()));
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn propagate_collection_changes<T: FrameTarget>(
+    mut commands: Commands,
+    pending: Res<PendingCollectionChanges>,
+    repos: Res<AllAnimationResource>,
+    assets: Res<Assets<AnimationCollection>>,
+    mut targets: Query<(
+        Entity,
+        &mut AnimationComp,
+        &mut T,
+        Option<&mut Handle<TextureAtlas>>,
+        Option<&mut AnimationPrecentProgress>,
+        Option<&ListenLifecycle>,
+    )>,
+    mut on_fall_back: EventWriter<AnimationFellBackToStart>,
+    mut on_lifecycle: EventWriter<AnimationLifecycle>,
+) {
+    let mut lifecycle: Vec<AnimationLifecycle> = Vec::new();
+    for (id, changes) in pending.0.iter() {
+        let collection = match id {
+            CollectionId::Key(key) => repos.animation_under(key).ok(),
+            CollectionId::Asset(asset_id) => assets.get(*asset_id),
+        };
+        for (who, mut animator, mut target, atlas, progress, listen_lifecycle) in targets.iter_mut()
+        {
+            if !animator.refers_to(id) {
+                continue;
+            }
+            let Some(collection) = collection else {
+                // Like `EndBehavior::RemoveAnimation`, so the removed collection is not looked up
+                // every frame. The entity keeps its current frame.
+                ListenLifecycle::report(
+                    listen_lifecycle,
+                    &mut lifecycle,
+                    who,
+                    LifecycleKind::CollectionChanged,
+                    &animator,
+                    target.index(),
+                );
+                commands.entity(who).remove::<AnimationComp>();
+                continue;
+            };
+            if let Some(mut atlas) = atlas.filter(|atlas| **atlas != collection.atlas()) {
+                *atlas = collection.atlas();
+            }
+            let previous_state = animator.current_state.clone();
+            let result = animator.apply_collection_changes(target.index_mut(), changes, collection);
+            if let Ok(true) = result {
+                on_fall_back.send(AnimationFellBackToStart {
+                    who,
+                    collection: *id,
+                    removed_state: previous_state,
+                    start_state: collection.start_state(),
+                });
+            }
+            if let Some(mut progress) = progress.filter(|progress| !progress.manual) {
+                if let Ok(frames) = collection.get_frames_under(animator.current_state()) {
+                    progress.progress = frames.precent(target.index());
+                }
+            }
+            ListenLifecycle::report(
+                listen_lifecycle,
+                &mut lifecycle,
+                who,
+                LifecycleKind::CollectionChanged,
+                &animator,
+                target.index(),
+            );
+            utils::log_if_error(
+                result.map(|_| ()),
+                "Applying changes of a collection to an animation failed.",
+            );
+        }
+    }
+    if !lifecycle.is_empty() {
+        on_lifecycle.send_batch(lifecycle);
+    }
+}
+
+/// Sends the changes once every [`FrameTarget`] was updated by [`propagate_collection_changes`].
+pub fn send_collection_changes(
+    mut pending: ResMut<PendingCollectionChanges>,
+    mut on_change: EventWriter<AnimationCollectionChanged>,
+) {
+    if pending.0.is_empty() {
+        return;
+    }
+    on_change.send_batch(pending.0.drain(..).map(|(collection, changes)| {
+        AnimationCollectionChanged {
+            collection,
+            changes,
+        }
+    }));
+}
+
+#[cfg(test)]
+mod testing {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        prelude::AnimationCollectionBuilder,
+        test_app::{self, index, COLUMNS, KEY},
+    };
+
+    /// Sprite playing `Idle` on the first row, 250 ms per frame.
+    fn app_at(elapsed: Duration) -> (App, Entity) {
+        let mut app = test_app::app();
+        let time = test_app::ROW_TIME;
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_row_ani("Idle", 0, time, true)
+            .add_row_ani("Walk", 1, time, true)
+            .build("Idle");
+        test_app::add_collection(&mut app, collection);
+        let sprite = test_app::spawn_sprite(&mut app);
+        test_app::tick(&mut app, Duration::ZERO);
+        test_app::tick(&mut app, elapsed);
+        (app, sprite)
+    }
+
+    fn change_state(app: &mut App, state: &str, change: impl FnOnce(&mut AnimationCollection)) {
+        let mut repos = app.world.resource_mut::<AllAnimationResource>();
+        let collection = repos.animation_under_mut(KEY).unwrap();
+        assert!(collection.get_frames_under(state).is_ok());
+        change(collection);
+        test_app::tick(app, Duration::ZERO);
+    }
+
+    #[test]
+    fn clamps_index_into_shortened_state() {
+        let (mut app, sprite) = app_at(Duration::from_millis(800));
+        assert_eq!(3, index(&app, sprite));
+
+        change_state(&mut app, "Idle", |collection| {
+            collection
+                .modify_state("Idle", |frames| frames.set_indexes(0, 1).unwrap())
+                .unwrap();
+        });
+        assert_eq!(1, index(&app, sprite));
+        assert!(test_app::drain_events::<AnimationFellBackToStart>(&mut app).is_empty());
+    }
+
+    #[test]
+    fn keeps_progress_in_moved_row() {
+        let (mut app, sprite) = app_at(Duration::from_millis(600));
+        assert_eq!(2, index(&app, sprite));
+
+        let row = 2 * COLUMNS;
+        change_state(&mut app, "Idle", |collection| {
+            collection
+                .modify_state("Idle", |frames| {
+                    frames.set_indexes(row, row + COLUMNS - 1).unwrap();
+                })
+                .unwrap();
+        });
+        assert_eq!(row + 2, index(&app, sprite));
+        assert_eq!(
+            "Idle",
+            app.world
+                .get::<AnimationComp>(sprite)
+                .unwrap()
+                .current_state()
+        );
+    }
+
+    #[test]
+    fn falls_back_to_start_for_removed_state() {
+        let (mut app, sprite) = app_at(Duration::ZERO);
+        app.world
+            .get_mut::<AnimationComp>(sprite)
+            .unwrap()
+            .set_state("Walk");
+        test_app::tick(&mut app, Duration::from_millis(300));
+        assert_eq!(COLUMNS + 1, index(&app, sprite));
+
+        change_state(&mut app, "Walk", |collection| {
+            collection.remove_state("Walk").unwrap();
+        });
+        let animator = app.world.get::<AnimationComp>(sprite).unwrap();
+        assert_eq!("Idle", animator.current_state());
+        assert_eq!(0, index(&app, sprite));
+        let fell_back = test_app::drain_events::<AnimationFellBackToStart>(&mut app);
+        assert!(matches!(
+            fell_back.as_slice(),
+            [AnimationFellBackToStart { who, removed_state, start_state: "Idle", .. }]
+                if *who == sprite && removed_state == "Walk"
+        ));
+    }
+
+    #[cfg(feature = "assets")]
+    #[test]
+    fn falls_back_to_new_start_state_on_hot_reload() {
+        use bevy::ecs::system::SystemState;
+
+        use crate::save_load::AnimationAssets;
+
+        fn animations(start_state: &str, frames: &str) -> AnimationAssets {
+            ron::from_str(&format!(
+                r#"{{
+                    start_state: "{start_state}",
+                    frames: [{frames}],
+                    rows: 4,
+                    columns: 4,
+                    cell_size: (16, 16),
+                }}"#
+            ))
+            .unwrap()
+        }
+
+        let mut app = test_app::app();
+        let handle = app
+            .world
+            .resource_mut::<Assets<AnimationAssets>>()
+            .add(animations(
+                "Idle",
+                r#"(name: "Idle", start_row: 0), (name: "Walk", start_row: 1)"#,
+            ));
+        let mut state = SystemState::<(
+            ResMut<AllAnimationResource>,
+            ResMut<Assets<TextureAtlas>>,
+            Res<Assets<AnimationAssets>>,
+        )>::new(&mut app.world);
+        let (mut repos, mut atlases, assets) = state.get_mut(&mut app.world);
+        repos
+            .add_from_asset(
+                handle.clone(),
+                Handle::default(),
+                &mut atlases,
+                &assets,
+                Some(KEY),
+            )
+            .unwrap();
+        let sprite = test_app::spawn_sprite(&mut app);
+        test_app::tick(&mut app, Duration::ZERO);
+        test_app::switch_to(&mut app, sprite, "Walk");
+
+        let without_walk = r#"(name: "Idle", start_row: 0), (name: "Run", start_row: 2)"#;
+        app.world
+            .resource_mut::<Assets<AnimationAssets>>()
+            .insert(handle.id(), animations("Run", without_walk));
+        // Asset events are sent at the end of an update and read in the next one.
+        test_app::tick(&mut app, Duration::ZERO);
+        test_app::tick(&mut app, Duration::ZERO);
+
+        let animator = app.world.get::<AnimationComp>(sprite).unwrap();
+        assert_eq!("Run", animator.current_state());
+        assert_eq!(2 * COLUMNS, index(&app, sprite));
+        let fell_back = test_app::drain_events::<AnimationFellBackToStart>(&mut app);
+        assert!(matches!(
+            fell_back.as_slice(),
+            [AnimationFellBackToStart { who, removed_state, start_state: "Run", .. }]
+                if *who == sprite && removed_state == "Walk"
+        ));
+
+        app.world
+            .resource_mut::<Assets<AnimationAssets>>()
+            .insert(handle.id(), animations("Jump", without_walk));
+        let (mut repos, _, assets) = state.get_mut(&mut app.world);
+        assert!(repos.replace_from_assets(&handle.id(), &assets).is_err());
+        assert_eq!("Run", repos.animation_under(KEY).unwrap().start_state());
+    }
+}
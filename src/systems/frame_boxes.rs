@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation_comp::AnimationComp,
+    collection_look_up::AnimationCollections,
+    frame_boxes::{AnimationBoxGizmos, AnimationBoxes, FrameBox},
+    frame_target::FrameTarget,
+    systems::frame_space::FrameSpaceParts,
+};
+
+#[allow(clippy::type_complexity)]
+pub fn update_animation_boxes<T: FrameTarget>(
+    mut query: Query<
+        (&AnimationComp, &T, FrameSpaceParts, &mut AnimationBoxes),
+        Or<(Changed<T>, Added<AnimationBoxes>)>,
+    >,
+    repos: AnimationCollections,
+    images: Res<Assets<Image>>,
+) {
+    for (animator, target, parts, mut boxes) in query.iter_mut() {
+        let Ok(collection) = animator.get_collection(&repos) else {
+            continue;
+        };
+        let Ok(frames) = collection.get_frames_under(animator.current_state()) else {
+            continue;
+        };
+        let space = parts.space(target, &frames, collection.grid(), &images);
+        let new_boxes: Vec<FrameBox> = frames
+            .boxes_at(target.index())
+            .iter()
+            .map(|frame_box| frame_box.in_space(&space))
+            .collect();
+        if boxes.bypass_change_detection().set_if_changed(new_boxes) {
+            boxes.set_changed();
+        }
+    }
+}
+
+pub fn draw_animation_boxes(
+    query: Query<(&AnimationBoxes, &GlobalTransform, &ViewVisibility)>,
+    settings: Res<AnimationBoxGizmos>,
+    mut gizmos: Gizmos,
+) {
+    for (boxes, transform, visibility) in query.iter() {
+        if !visibility.get() {
+            continue;
+        }
+        let (scale, rotation, _) = transform.to_scale_rotation_translation();
+        for frame_box in boxes.iter() {
+            gizmos.rect(
+                transform.transform_point(frame_box.rect.center().extend(0.)),
+                rotation,
+                frame_box.rect.size() * scale.truncate(),
+                settings.color(frame_box.name),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        animation_frames::AnimationFrames,
+        animation_respo_resource::AllAnimationResource,
+        frame_target::SpriteRectFrame,
+        prelude::AnimationCollectionBuilder,
+        test_app::{self, KEY},
+    };
+
+    #[test]
+    fn updates_boxes_of_every_frame_target() {
+        let mut app = test_app::app();
+        let mut frames =
+            AnimationFrames::from_row(0, test_app::ROW_TIME, test_app::COLUMNS, true).unwrap();
+        let hit = |min: Vec2, max: Vec2| FrameBox::new("hit", Rect::from_corners(min, max));
+        frames.set_frame_boxes(0, vec![hit(Vec2::new(2., -4.), Vec2::new(6., 4.))]);
+        frames.set_frame_boxes(1, vec![hit(Vec2::new(-8., 0.), Vec2::new(0., 8.))]);
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_animation("Idle", frames)
+            .build("Idle");
+        test_app::add_collection(&mut app, collection);
+        let sheet = test_app::spawn_sprite(&mut app);
+        let bundle = app
+            .world
+            .resource::<AllAnimationResource>()
+            .create_sprite_rect_comp(KEY, Handle::default())
+            .unwrap();
+        let rect = app.world.spawn(bundle).id();
+        for entity in [sheet, rect] {
+            app.world
+                .entity_mut(entity)
+                .insert(AnimationBoxes::default());
+        }
+        let hit_of =
+            |app: &App, entity: Entity| app.world.get::<AnimationBoxes>(entity).unwrap().get("hit");
+
+        test_app::tick(&mut app, Duration::ZERO);
+        for entity in [sheet, rect] {
+            assert_eq!(
+                Some(Rect::new(2., -4., 6., 4.)),
+                hit_of(&app, entity),
+                "{entity:?}"
+            );
+        }
+
+        app.world.get_mut::<SpriteRectFrame>(rect).unwrap().flip.x = true;
+        test_app::tick(&mut app, Duration::ZERO);
+        assert_eq!(Some(Rect::new(-6., -4., -2., 4.)), hit_of(&app, rect));
+
+        test_app::tick(&mut app, Duration::from_millis(250));
+        assert_eq!(Some(Rect::new(-8., 0., 0., 8.)), hit_of(&app, sheet));
+        assert_eq!(Some(Rect::new(0., 0., 8., 8.)), hit_of(&app, rect));
+    }
+}
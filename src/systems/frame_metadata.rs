@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation_comp::AnimationComp, collection_look_up::AnimationCollections,
+    frame_metadata::FrameMetadata, frame_target::FrameTarget,
+};
+
+#[allow(clippy::type_complexity)]
+pub fn update_frame_metadata<T: FrameTarget>(
+    mut query: Query<
+        (&AnimationComp, &T, &mut FrameMetadata),
+        Or<(Changed<T>, Added<FrameMetadata>)>,
+    >,
+    repos: AnimationCollections,
+) {
+    for (animator, target, mut metadata) in query.iter_mut() {
+        let Ok(frames) = animator.get_current_seq(&repos) else {
+            continue;
+        };
+        if metadata
+            .bypass_change_detection()
+            .set_if_changed(frames.metadata_at(target.index()))
+        {
+            metadata.set_changed();
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{animation_frames::AnimationFrames, prelude::AnimationCollectionBuilder, test_app};
+
+    #[test]
+    fn marks_changed_only_for_different_values() {
+        let mut app = test_app::app();
+        test_app::count_changes::<FrameMetadata>(&mut app);
+        let mut frames =
+            AnimationFrames::from_row(0, test_app::ROW_TIME, test_app::COLUMNS, true).unwrap();
+        frames.insert_frame_value(0, "invulnerable", true);
+        frames.insert_frame_value(1, "invulnerable", true);
+        frames.insert_frame_value(3, "surface", "stone");
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_animation("Idle", frames)
+            .build("Idle");
+        test_app::add_collection(&mut app, collection);
+        let sprite = test_app::spawn_sprite(&mut app);
+        app.world
+            .entity_mut(sprite)
+            .insert(FrameMetadata::default());
+        let changes = test_app::changes::<FrameMetadata>;
+        let metadata = |app: &App| app.world.get::<FrameMetadata>(sprite).unwrap().clone();
+
+        test_app::tick(&mut app, Duration::ZERO);
+        assert!(metadata(&app).flag("invulnerable"));
+        assert_eq!(1, changes(&app));
+
+        test_app::tick(&mut app, Duration::from_millis(250));
+        assert!(metadata(&app).flag("invulnerable"));
+        assert_eq!(1, changes(&app));
+
+        test_app::tick(&mut app, Duration::from_millis(250));
+        assert_eq!(0, metadata(&app).iter().count());
+        assert_eq!(2, changes(&app));
+
+        test_app::tick(&mut app, Duration::from_millis(250));
+        assert_eq!(Some("stone"), metadata(&app).text("surface"));
+        assert!(!metadata(&app).contains("invulnerable"));
+        assert_eq!(3, changes(&app));
+    }
+}
@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation_comp::AnimationComp, collection_look_up::AnimationCollections,
+    frame_sockets::FollowSocket, frame_target::FrameTarget, systems::frame_space::FrameSpaceParts,
+};
+
+pub fn follow_sockets<T: FrameTarget>(
+    mut followers: Query<(&FollowSocket, &Parent, &mut Transform)>,
+    parents: Query<(&AnimationComp, &T, FrameSpaceParts)>,
+    repos: AnimationCollections,
+    images: Res<Assets<Image>>,
+) {
+    for (follow, parent, mut transform) in followers.iter_mut() {
+        let Ok((animator, target, parts)) = parents.get(parent.get()) else {
+            continue;
+        };
+        let Ok(collection) = animator.get_collection(&repos) else {
+            continue;
+        };
+        let Ok(frames) = collection.get_frames_under(animator.current_state()) else {
+            continue;
+        };
+        let Some(socket) = frames.socket_at(target.index(), follow.name) else {
+            continue;
+        };
+        let space = parts.space(target, &frames, collection.grid(), &images);
+        let socket_transform = socket.transform_in(&space);
+        let new_transform = Transform {
+            translation: socket_transform
+                .translation
+                .truncate()
+                .extend(transform.translation.z),
+            rotation: socket_transform.rotation,
+            scale: transform.scale.abs() * socket_transform.scale,
+        };
+        if *transform != new_transform {
+            *transform = new_transform;
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use std::{f32::consts::FRAC_PI_4, time::Duration};
+
+    use super::*;
+    use crate::{
+        animation_frames::AnimationFrames, frame_sockets::FrameSocket,
+        prelude::AnimationCollectionBuilder, test_app,
+    };
+
+    #[test]
+    fn moves_child_to_socket_and_mirrors_it() {
+        let mut app = test_app::app();
+        let mut frames =
+            AnimationFrames::from_row(0, test_app::ROW_TIME, test_app::COLUMNS, true).unwrap();
+        frames.set_frame_sockets(
+            0,
+            vec![FrameSocket::new("hand", Vec2::new(4., 2.)).with_rotation(FRAC_PI_4)],
+        );
+        frames.set_frame_sockets(1, vec![FrameSocket::new("hand", Vec2::new(6., -2.))]);
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_animation("Idle", frames)
+            .build("Idle");
+        test_app::add_collection(&mut app, collection);
+        let sprite = test_app::spawn_sprite(&mut app);
+        let weapon = app
+            .world
+            .spawn((
+                FollowSocket::new("hand"),
+                Transform::from_xyz(0., 0., 1.).with_scale(Vec3::splat(2.)),
+            ))
+            .id();
+        app.world.entity_mut(sprite).add_child(weapon);
+        let transform = |app: &App| *app.world.get::<Transform>(weapon).unwrap();
+
+        test_app::tick(&mut app, Duration::ZERO);
+        assert_eq!(Vec3::new(4., 2., 1.), transform(&app).translation);
+        assert_eq!(Quat::from_rotation_z(FRAC_PI_4), transform(&app).rotation);
+
+        test_app::tick(&mut app, Duration::from_millis(250));
+        assert_eq!(Vec3::new(6., -2., 1.), transform(&app).translation);
+        assert_eq!(Vec3::splat(2.), transform(&app).scale);
+
+        app.world
+            .get_mut::<TextureAtlasSprite>(sprite)
+            .unwrap()
+            .flip_x = true;
+        test_app::tick(&mut app, Duration::ZERO);
+        assert_eq!(Vec3::new(-6., -2., 1.), transform(&app).translation);
+        assert_eq!(Vec3::new(-2., 2., 2.), transform(&app).scale);
+
+        // Keeps the transform on frames without the socket.
+        test_app::tick(&mut app, Duration::from_millis(250));
+        assert_eq!(Vec3::new(-6., -2., 1.), transform(&app).translation);
+    }
+
+    #[test]
+    fn follows_socket_of_sprite_rect() {
+        use crate::{animation_respo_resource::AllAnimationResource, test_app::KEY};
+
+        let mut app = test_app::app();
+        let mut frames =
+            AnimationFrames::from_row(0, test_app::ROW_TIME, test_app::COLUMNS, true).unwrap();
+        frames.set_frame_sockets(0, vec![FrameSocket::new("hand", Vec2::new(4., 2.))]);
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_animation("Idle", frames)
+            .build("Idle");
+        test_app::add_collection(&mut app, collection);
+        let mut bundle = app
+            .world
+            .resource::<AllAnimationResource>()
+            .create_sprite_rect_comp(KEY, Handle::default())
+            .unwrap();
+        bundle.sprite.sprite.custom_size = Some(Vec2::splat(32.));
+        let sprite = app.world.spawn(bundle).id();
+        let weapon = app
+            .world
+            .spawn((FollowSocket::new("hand"), Transform::default()))
+            .id();
+        app.world.entity_mut(sprite).add_child(weapon);
+
+        test_app::tick(&mut app, Duration::ZERO);
+        let transform = app.world.get::<Transform>(weapon).unwrap();
+        assert_eq!(Vec3::new(8., 4., 0.), transform.translation);
+    }
+}
@@ -0,0 +1,48 @@
+use bevy::{ecs::query::WorldQuery, prelude::*};
+
+use crate::{
+    animation_altlas::AnimationAltlasMeta, animation_frames::AnimationFrames,
+    frame_target::FrameTarget, utils::SpriteSpace,
+};
+
+/// Components besides the [`FrameTarget`] which decide the size a frame is drawn at.
+#[derive(WorldQuery)]
+pub struct FrameSpaceParts {
+    sprite_sheet: Option<&'static TextureAtlasSprite>,
+    sprite: Option<&'static Sprite>,
+    node: Option<&'static Node>,
+    image: Option<&'static Handle<Image>>,
+}
+
+impl FrameSpacePartsItem<'_> {
+    /// Space of the current frame of `target`.
+    /// A frame made of a separate image has the size of the image once it is loaded,
+    /// every other frame has the cell size of `grid`.
+    /// Nodes of `bevy_ui` use this space as well, although their layout has y pointing down.
+    pub fn space<T: FrameTarget>(
+        &self,
+        target: &T,
+        frames: &AnimationFrames,
+        grid: &AnimationAltlasMeta,
+        images: &Assets<Image>,
+    ) -> SpriteSpace {
+        let frame_size = match frames.image_at(target.index()) {
+            Some(_) => self
+                .image
+                .and_then(|image| images.get(image))
+                .map(Image::size_f32)
+                .unwrap_or_default(),
+            None => grid.cell_size(),
+        };
+        let size = self
+            .sprite_sheet
+            .and_then(|sprite| sprite.custom_size)
+            .or(self.sprite.and_then(|sprite| sprite.custom_size))
+            .or(self
+                .node
+                .map(Node::size)
+                .filter(|size| size.cmpgt(Vec2::ZERO).all()));
+        let anchor = target.anchor().unwrap_or_default();
+        SpriteSpace::from_parts(target.flip(), anchor, size, frame_size)
+    }
+}
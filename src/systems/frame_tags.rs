@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation_comp::AnimationComp,
+    collection_look_up::AnimationCollections,
+    frame_tags::{ActiveTags, FrameTagEntered, FrameTagExited},
+    frame_target::FrameTarget,
+};
+
+pub fn report_frame_tags<T: FrameTarget>(
+    mut query: Query<(Entity, &mut AnimationComp, &T)>,
+    repos: AnimationCollections,
+    mut on_enter: EventWriter<FrameTagEntered>,
+    mut on_exit: EventWriter<FrameTagExited>,
+) {
+    for (who, mut animator, target) in query.iter_mut() {
+        // Only bookkeeping of this system is changed.
+        let animator = animator.bypass_change_detection();
+        let skipped = std::mem::take(&mut animator.skipped_frames);
+        let Ok(frames) = animator.get_current_seq(&repos) else {
+            continue;
+        };
+        let same_state = animator
+            .active_tags
+            .as_ref()
+            .is_some_and(|active| active.state == animator.current_state);
+        if !frames.has_tags() && (same_state || animator.active_tags.is_none()) {
+            continue;
+        }
+        let mut active = match animator.active_tags.take() {
+            Some(active) if same_state => active,
+            previous => {
+                if let Some(previous) = previous {
+                    on_exit.send_batch(previous.tags.into_iter().map(|tag| FrameTagExited {
+                        who,
+                        state: previous.state.clone(),
+                        tag,
+                    }));
+                }
+                ActiveTags {
+                    state: animator.current_state.clone(),
+                    tags: Vec::new(),
+                }
+            }
+        };
+
+        // Frames skipped within one update still enter and exit their tags.
+        for index in skipped.into_iter().chain([target.index()]) {
+            let now: Vec<&'static str> = frames.tags_at(index).collect();
+            for tag in active.tags.iter().filter(|tag| !now.contains(tag)) {
+                on_exit.send(FrameTagExited {
+                    who,
+                    state: active.state.clone(),
+                    tag,
+                });
+            }
+            for tag in now.iter().filter(|tag| !active.tags.contains(tag)) {
+                on_enter.send(FrameTagEntered {
+                    who,
+                    state: active.state.clone(),
+                    tag,
+                });
+            }
+            active.tags = now;
+        }
+        animator.active_tags = Some(active);
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        animation_frames::AnimationFrames, frame_tags::FrameTag,
+        prelude::AnimationCollectionBuilder, test_app,
+    };
+
+    fn tags<E>(app: &mut App, tag: impl Fn(&E) -> &'static str) -> Vec<&'static str>
+    where
+        E: Event,
+    {
+        test_app::drain_events::<E>(app).iter().map(tag).collect()
+    }
+
+    #[test]
+    fn reports_skipped_tags_and_exits_on_state_change() {
+        let mut app = test_app::app();
+        let attack = AnimationFrames::from_row(0, test_app::ROW_TIME, test_app::COLUMNS, true)
+            .unwrap()
+            .with_tag(FrameTag::new("active", 1, 1))
+            .unwrap()
+            .with_tag(FrameTag::new("recover", 2, 3))
+            .unwrap();
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_animation("Attack", attack)
+            .add_row_ani("Idle", 1, test_app::ROW_TIME, true)
+            .build("Attack");
+        test_app::add_collection(&mut app, collection);
+        let sprite = test_app::spawn_sprite(&mut app);
+        test_app::tick(&mut app, Duration::ZERO);
+        let entered = |app: &mut App| tags(app, |event: &FrameTagEntered| event.tag);
+        let exited = |app: &mut App| tags(app, |event: &FrameTagExited| event.tag);
+        assert!(entered(&mut app).is_empty());
+
+        // The single frame of "active" is skipped within one update.
+        test_app::tick(&mut app, Duration::from_millis(500));
+        assert_eq!(vec!["active", "recover"], entered(&mut app));
+        assert_eq!(vec!["active"], exited(&mut app));
+
+        test_app::switch_to(&mut app, sprite, "Idle");
+        assert!(entered(&mut app).is_empty());
+        let exits = test_app::drain_events::<FrameTagExited>(&mut app);
+        assert!(matches!(
+            exits.as_slice(),
+            [FrameTagExited { who, state, tag: "recover" }] if *who == sprite && state == "Attack"
+        ));
+    }
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation_comp::AnimationComp, collection_look_up::AnimationCollections,
+    frame_target::ImageFrame,
+};
+
+pub fn swap_frame_images(
+    mut query: Query<
+        (
+            &AnimationComp,
+            &mut ImageFrame,
+            &mut Handle<Image>,
+            &mut Sprite,
+        ),
+        Changed<ImageFrame>,
+    >,
+    repos: AnimationCollections,
+    asset_server: Res<AssetServer>,
+) {
+    for (animator, mut frame, mut image, mut sprite) in query.iter_mut() {
+        let frame = frame.bypass_change_detection();
+        sprite.flip_x = frame.flip.x;
+        sprite.flip_y = frame.flip.y;
+        sprite.anchor = frame.anchor;
+        let Ok(frames) = animator.get_current_seq(&repos) else {
+            continue;
+        };
+        if let Some(path) = frames.image_at(frame.index) {
+            let current: Handle<Image> = asset_server.load(path.to_string());
+            if *image != current {
+                *image = current;
+            }
+        }
+        frame.preloaded = frames
+            .images_after(frame.index, frame.preload)
+            .map(|path| asset_server.load(path.to_string()))
+            .collect();
+    }
+}
@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation_comp::AnimationComp, collection_look_up::AnimationCollections,
+    frame_target::SpriteRectFrame,
+};
+
+pub fn write_sprite_rects(
+    mut query: Query<(&AnimationComp, &SpriteRectFrame, &mut Sprite), Changed<SpriteRectFrame>>,
+    repos: AnimationCollections,
+) {
+    for (animator, frame, mut sprite) in query.iter_mut() {
+        if let Ok(collection) = animator.get_collection(&repos) {
+            frame.write_to(&mut sprite, collection.grid());
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Runs everything of [`BoolAnimationPlugin`](crate::prelude::BoolAnimationPlugin)
+//! without rendering. The virtual time is only advanced by [`tick`].
+use std::{marker::PhantomData, time::Duration};
+
+use bevy::prelude::*;
+
+use crate::{
+    animation_comp::AnimationComp,
+    animation_respo_resource::AllAnimationResource,
+    bool_animation_plugin,
+    prelude::{AnimationAltlas, AnimationAltlasMeta, AnimationCollection, AnimationDuration},
+};
+
+/// Key under which [`add_collection`] adds a collection.
+pub(crate) const KEY: &str = "Test";
+/// Columns of the grid returned by [`grid`].
+pub(crate) const COLUMNS: usize = 4;
+/// Time of a state over a row of [`grid`], so that every frame is shown for 250 ms.
+pub(crate) const ROW_TIME: AnimationDuration = AnimationDuration::from_millis(750);
+
+pub(crate) fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        TaskPoolPlugin::default(),
+        TypeRegistrationPlugin,
+        AssetPlugin::default(),
+    ))
+    .init_asset::<Image>()
+    .init_asset::<TextureAtlas>()
+    .init_resource::<Time<Virtual>>();
+    bool_animation_plugin::add_animation(&mut app);
+    app
+}
+
+/// Advances the virtual time by `delta` and runs one update.
+pub(crate) fn tick(app: &mut App, delta: Duration) {
+    app.world.resource_mut::<Time<Virtual>>().advance_by(delta);
+    app.update();
+}
+
+/// Grid of 4 x 4 cells with 16 pixels each.
+pub(crate) fn grid(app: &mut App) -> AnimationAltlas {
+    AnimationAltlasMeta::new(COLUMNS, COLUMNS, Vec2::splat(16.)).build(
+        Handle::default(),
+        &mut app.world.resource_mut::<Assets<TextureAtlas>>(),
+    )
+}
+
+pub(crate) fn add_collection(app: &mut App, collection: AnimationCollection) {
+    app.world
+        .resource_mut::<AllAnimationResource>()
+        .try_add_animations(KEY, collection)
+        .unwrap();
+}
+
+/// Spawns a sprite playing the start state of the collection under [`KEY`].
+pub(crate) fn spawn_sprite(app: &mut App) -> Entity {
+    let bundle = app
+        .world
+        .resource::<AllAnimationResource>()
+        .create_sprite_comp(KEY)
+        .unwrap();
+    app.world.spawn(bundle).id()
+}
+
+pub(crate) fn drain_events<E: Event>(app: &mut App) -> Vec<E> {
+    app.world.resource_mut::<Events<E>>().drain().collect()
+}
+
+/// Index of the `TextureAtlasSprite` of `sprite`.
+pub(crate) fn index(app: &App, sprite: Entity) -> usize {
+    app.world.get::<TextureAtlasSprite>(sprite).unwrap().index
+}
+
+/// Sets the state of `entity` and applies it in an update without advancing the time.
+pub(crate) fn switch_to(app: &mut App, entity: Entity, state: &'static str) {
+    app.world
+        .get_mut::<AnimationComp>(entity)
+        .unwrap()
+        .set_state(state);
+    tick(app, Duration::ZERO);
+}
+
+/// How often a component `C` was marked as changed at the end of the updates.
+#[derive(Resource)]
+struct Changes<C>(usize, PhantomData<C>);
+
+/// Starts counting the changes of `C`, which are read via [`changes`].
+pub(crate) fn count_changes<C: Component>(app: &mut App) {
+    fn count<C: Component>(query: Query<(), Changed<C>>, mut changes: ResMut<Changes<C>>) {
+        changes.0 += query.iter().count();
+    }
+
+    app.insert_resource(Changes::<C>(0, PhantomData))
+        .add_systems(Last, count::<C>);
+}
+
+pub(crate) fn changes<C: Component>(app: &App) -> usize {
+    app.world.resource::<Changes<C>>().0
+}
@@ -69,6 +69,7 @@ mod testing {
         let data = String::from("reference");
         let text_like = TextLike::from(&data);
         assert_eq!(TextLike::Ref(&data), text_like);
+        #[allow(clippy::needless_borrows_for_generic_args)]
         test_ref(&String::from("aaa"));
         test_static("aaa");
         test_owned(String::from("aaa"));
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation_collection::AnimationCollection, animation_comp::AnimationComp,
+    animation_time_factor::AnimationTimeScale, collection_look_up::start_of_asset,
+    types::KeyLookUpResult,
+};
+
+/// Same as [`SpriteAnimationBundle`](crate::prelude::SpriteAnimationBundle) for a node of `bevy_ui`.
+#[derive(Bundle)]
+pub struct UiAnimationBundle {
+    pub image: AtlasImageBundle,
+    pub frames: AnimationComp,
+    pub time_scale: AnimationTimeScale,
+}
+
+impl UiAnimationBundle {
+    /// Same as [`SpriteAnimationBundle::from_asset`](crate::prelude::SpriteAnimationBundle::from_asset).
+    pub fn from_asset(
+        collection: Handle<AnimationCollection>,
+        assets: &Assets<AnimationCollection>,
+    ) -> KeyLookUpResult<Self> {
+        let (frames, animations, start_index) = start_of_asset(collection, assets)?;
+        Ok(Self::new(frames, animations, start_index))
+    }
+
+    pub(crate) fn new(
+        frames: AnimationComp,
+        animations: &AnimationCollection,
+        start_index: usize,
+    ) -> Self {
+        let mut image = UiTextureAtlasImage {
+            index: start_index,
+            ..default()
+        };
+        if let Some(mirror) = animations
+            .get_frames_under(frames.current_state())
+            .ok()
+            .and_then(|frames| frames.mirror())
+        {
+            mirror.apply(&mut image);
+        }
+        Self {
+            image: AtlasImageBundle {
+                texture_atlas: animations.atlas(),
+                texture_atlas_image: image,
+                ..default()
+            },
+            time_scale: Default::default(),
+            frames,
+        }
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        animation_frames::{AnimationFrames, Mirror},
+        animation_respo_resource::AllAnimationResource,
+        prelude::AnimationCollectionBuilder,
+        test_app::{self, COLUMNS, KEY},
+    };
+
+    #[test]
+    fn animates_ui_image() {
+        let mut app = test_app::app();
+        let left = AnimationFrames::from_row(0, test_app::ROW_TIME, COLUMNS, true).unwrap();
+        let collection = AnimationCollectionBuilder::new(test_app::grid(&mut app))
+            .add_animation("Right", left.mirrored(Mirror { x: true, y: false }))
+            .add_animation("Left", left)
+            .add_row_ani("Up", 1, test_app::ROW_TIME, true)
+            .build("Left");
+        test_app::add_collection(&mut app, collection);
+        let bundle = app
+            .world
+            .resource::<AllAnimationResource>()
+            .create_ui_comp(KEY)
+            .unwrap();
+        let node = app.world.spawn(bundle).id();
+        let image = |app: &App| {
+            let image = app.world.get::<UiTextureAtlasImage>(node).unwrap();
+            (image.index, image.flip_x)
+        };
+
+        test_app::tick(&mut app, Duration::ZERO);
+        test_app::tick(&mut app, Duration::from_millis(250));
+        assert_eq!((1, false), image(&app));
+
+        test_app::switch_to(&mut app, node, "Right");
+        assert_eq!((0, true), image(&app));
+        test_app::tick(&mut app, Duration::from_millis(500));
+        assert_eq!((2, true), image(&app));
+
+        test_app::switch_to(&mut app, node, "Up");
+        assert_eq!((COLUMNS, false), image(&app));
+    }
+}
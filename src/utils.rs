@@ -1,7 +1,7 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, sprite::Anchor};
 use std::error::Error;
 
-use crate::types::AnimationIndex;
+use crate::{animation_frames::Mirror, frame_target::FrameTarget, types::AnimationIndex};
 
 pub fn index_from_row_column(
     row: AnimationIndex,
@@ -28,3 +28,40 @@ pub fn indent_succive(to_ident: &str, pad_amount: usize) -> String {
         .collect::<Vec<String>>()
         .join("\n")
 }
+
+/// Moves points of a frame shown via a sprite into the local space of its entity.
+/// Takes flip, anchor and custom size of the sprite into account.
+pub struct SpriteSpace {
+    /// Is -1 on a flipped axis and 1 otherwise.
+    pub flip: Vec2,
+    scale: Vec2,
+    offset: Vec2,
+}
+
+impl SpriteSpace {
+    /// `frame_size` is the size of the frame in its sprite sheet.
+    pub fn new(sprite: &TextureAtlasSprite, frame_size: Vec2) -> Self {
+        Self::from_parts(sprite.flip(), sprite.anchor, sprite.custom_size, frame_size)
+    }
+
+    /// Same as [`Self::new`] for a frame drawn with `size` instead of its `frame_size` if given.
+    pub fn from_parts(flip: Mirror, anchor: Anchor, size: Option<Vec2>, frame_size: Vec2) -> Self {
+        let size = size.unwrap_or(frame_size);
+        let scale = if frame_size.cmpgt(Vec2::ZERO).all() {
+            size / frame_size
+        } else {
+            Vec2::ONE
+        };
+        let flip = Vec2::new(if flip.x { -1. } else { 1. }, if flip.y { -1. } else { 1. });
+        Self {
+            flip,
+            scale,
+            offset: -anchor.as_vec() * size,
+        }
+    }
+
+    /// `point` is in pixels relative to the center of the frame with y pointing up.
+    pub fn point(&self, point: Vec2) -> Vec2 {
+        point * self.flip * self.scale + self.offset
+    }
+}